@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Error;
+use aoc::http::UreqHttp;
+use aoc::{default_config, input_path, session_token, Fetched, Fetcher, SESSION_VAR, USER_AGENT};
+use clap::{Parser, Subcommand};
+use tracing::Level;
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023 workspace tools"
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Download puzzle inputs to day-NN/data/input.txt, skipping days already present
+    Fetch {
+        #[arg(long, required = true, help = "Day to fetch, repeat for more days")]
+        day: Vec<u32>,
+        #[arg(long, default_value_t = 2023)]
+        year: u32,
+        #[arg(
+            long,
+            help = "Workspace directory holding the day-NN crates",
+            default_value = "."
+        )]
+        root: PathBuf,
+        #[arg(
+            long,
+            help = "File holding the session token when AOC_SESSION is not set"
+        )]
+        session_file: Option<PathBuf>,
+        #[arg(long, default_value = USER_AGENT)]
+        user_agent: String,
+    },
+}
+
+fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_writer(std::io::stderr)
+        .init();
+    let args = Args::parse();
+    match args.command {
+        Command::Fetch {
+            day,
+            year,
+            root,
+            session_file,
+            user_agent,
+        } => {
+            // the token is only needed once a day is missing
+            let mut fetcher = None;
+            for day in day {
+                if input_path(&root, day).exists() {
+                    println!("day {}: cached {}", day, input_path(&root, day).display());
+                    continue;
+                }
+                if fetcher.is_none() {
+                    let config = session_file.clone().or_else(default_config);
+                    let session =
+                        session_token(std::env::var(SESSION_VAR).ok(), config.as_deref())?;
+                    let mut created = Fetcher::new(UreqHttp::new(Duration::from_secs(30)), session);
+                    created.year = year;
+                    created.user_agent = user_agent.clone();
+                    fetcher = Some(created);
+                }
+                if let Some(fetcher) = fetcher.as_mut() {
+                    match fetcher.fetch(&root, day)? {
+                        Fetched::Cached(path) => println!("day {}: cached {}", day, path.display()),
+                        Fetched::Downloaded(path) => {
+                            println!("day {}: downloaded {}", day, path.display())
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
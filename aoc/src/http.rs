@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use anyhow::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+    // seconds from a Retry-After header
+    pub retry_after: Option<u64>,
+}
+
+// The one request the fetcher makes, so tests can swap the backend.
+// Error statuses are responses, only transport failures are errors.
+pub trait Http {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, Error>;
+}
+
+pub struct UreqHttp {
+    agent: ureq::Agent,
+}
+
+impl UreqHttp {
+    pub fn new(timeout: Duration) -> Self {
+        UreqHttp {
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+}
+
+impl Http for UreqHttp {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, Error> {
+        let mut request = self.agent.get(url);
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(anyhow::anyhow!("GET {}: {}", url, e)),
+        };
+        let status = response.status();
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok());
+        let body = response
+            .into_string()
+            .map_err(|e| anyhow::anyhow!("GET {}: reading body: {}", url, e))?;
+        Ok(Response {
+            status,
+            body,
+            retry_after,
+        })
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Error;
+
+pub mod http;
+
+use http::Http;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = concat!(
+    "advent-of-code-2023-aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/jac18281828/advent-of-code-2023)"
+);
+// time between requests, and the longest Retry-After we wait out
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+pub const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+//
+// functions
+//
+
+pub fn input_path(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("day-{:02}", day))
+        .join("data")
+        .join("input.txt")
+}
+
+// $XDG_CONFIG_HOME/aoc/session, or ~/.config/aoc/session
+pub fn default_config() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("aoc").join("session"))
+}
+
+// the environment value wins over the config file, blank values do not count
+pub fn session_token(env: Option<String>, config: Option<&Path>) -> Result<String, Error> {
+    if let Some(token) = env.map(|token| token.trim().to_string()) {
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+    if let Some(config) = config {
+        if config.exists() {
+            let token = std::fs::read_to_string(config)
+                .map_err(|e| anyhow::anyhow!("{}: {}", config.display(), e))?;
+            let token = token.trim();
+            if !token.is_empty() {
+                return Ok(token.to_string());
+            }
+        }
+    }
+    Err(anyhow::anyhow!(
+        "no session token, set {} or write it to {}",
+        SESSION_VAR,
+        config.map_or("a config file".to_string(), |c| c.display().to_string())
+    ))
+}
+
+// Downloads puzzle inputs into the workspace, one request at a time and never
+// for a day that already has an input file.
+pub struct Fetcher<H: Http> {
+    http: H,
+    session: String,
+    pub base_url: String,
+    pub year: u32,
+    pub user_agent: String,
+    pub min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, session: String) -> Self {
+        Fetcher {
+            http,
+            session,
+            base_url: BASE_URL.to_string(),
+            year: 2023,
+            user_agent: USER_AGENT.to_string(),
+            min_interval: MIN_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    pub fn fetch(&mut self, root: &Path, day: u32) -> Result<Fetched, Error> {
+        if !(1..=25).contains(&day) {
+            return Err(anyhow::anyhow!("day {}: expected a day from 1 to 25", day));
+        }
+        let path = input_path(root, day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let body = self.download(day)?;
+        if body.is_empty() {
+            return Err(anyhow::anyhow!("day {}: empty input", day));
+        }
+        let parent = path.parent().unwrap_or(root);
+        std::fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("{}: {}", parent.display(), e))?;
+        // a partial write must not look like a cached input
+        let partial = path.with_extension("txt.part");
+        std::fs::write(&partial, body)
+            .and_then(|_| std::fs::rename(&partial, &path))
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    fn download(&mut self, day: u32) -> Result<String, Error> {
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            self.year,
+            day
+        );
+        let cookie = format!("session={}", self.session);
+        let user_agent = self.user_agent.clone();
+        let headers = [
+            ("User-Agent", user_agent.as_str()),
+            ("Cookie", cookie.as_str()),
+        ];
+        let mut retried = false;
+        loop {
+            self.wait_turn();
+            tracing::info!("GET {}", url);
+            let response = self.http.get(&url, &headers)?;
+            match response.status {
+                200 => return Ok(response.body),
+                400 => return Err(anyhow::anyhow!("day {}: session token rejected", day)),
+                404 => return Err(anyhow::anyhow!("day {}: input not available yet", day)),
+                429 => {
+                    let wait = response.retry_after.map(Duration::from_secs);
+                    match wait {
+                        Some(wait) if !retried && wait <= MAX_RETRY_WAIT => {
+                            tracing::warn!("rate limited, retrying in {:?}", wait);
+                            std::thread::sleep(wait);
+                            retried = true;
+                        }
+                        _ => {
+                            return Err(anyhow::anyhow!(
+                                "day {}: rate limited{}",
+                                day,
+                                wait.map_or(String::new(), |wait| format!(
+                                    ", retry after {}s",
+                                    wait.as_secs()
+                                ))
+                            ))
+                        }
+                    }
+                }
+                status => return Err(anyhow::anyhow!("day {}: HTTP {}", day, status)),
+            }
+        }
+    }
+
+    fn wait_turn(&mut self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::UreqHttp;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    // Stand-in server answering one connection per response in order,
+    // returns the base url and the request heads it saw
+    fn serve(responses: Vec<&'static str>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                requests.push(head);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    fn fetcher(base_url: String) -> Fetcher<UreqHttp> {
+        let mut fetcher = Fetcher::new(UreqHttp::new(Duration::from_secs(5)), "abc".to_string());
+        fetcher.base_url = base_url;
+        fetcher.min_interval = Duration::ZERO;
+        fetcher
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\n1\n2\n";
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let root = scratch("cache");
+        let (base_url, server) = serve(vec![OK]);
+        let mut fetcher = fetcher(base_url);
+        let path = input_path(&root, 3);
+        assert!(path.ends_with("day-03/data/input.txt"));
+        assert_eq!(
+            fetcher.fetch(&root, 3).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(fetcher.fetch(&root, 3).unwrap(), Fetched::Cached(path));
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        let head = requests[0].to_lowercase();
+        assert!(head.starts_with("get /2023/day/3/input http/1.1"));
+        assert!(head.contains("cookie: session=abc"));
+        assert!(head.contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let root = scratch("errors");
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3600\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let mut fetcher = fetcher(base_url);
+        let error = |result: Result<Fetched, Error>| result.unwrap_err().to_string();
+        assert_eq!(
+            error(fetcher.fetch(&root, 26)),
+            "day 26: expected a day from 1 to 25"
+        );
+        assert_eq!(
+            error(fetcher.fetch(&root, 1)),
+            "day 1: input not available yet"
+        );
+        assert_eq!(
+            error(fetcher.fetch(&root, 1)),
+            "day 1: rate limited, retry after 3600s"
+        );
+        assert!(!input_path(&root, 1).exists());
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let root = scratch("rate");
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            OK,
+            OK,
+        ]);
+        let mut fetcher = fetcher(base_url);
+        fetcher.min_interval = Duration::from_millis(100);
+        let start = Instant::now();
+        assert!(matches!(
+            fetcher.fetch(&root, 1).unwrap(),
+            Fetched::Downloaded(_)
+        ));
+        assert!(matches!(
+            fetcher.fetch(&root, 2).unwrap(),
+            Fetched::Downloaded(_)
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(server.join().unwrap().len(), 3);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_session_token() {
+        let root = scratch("session");
+        std::fs::create_dir_all(&root).unwrap();
+        let config = root.join("session");
+        assert!(session_token(None, Some(&config)).is_err());
+        std::fs::write(&config, "from-file\n").unwrap();
+        assert_eq!(
+            session_token(None, Some(&config)).unwrap(),
+            "from-file".to_string()
+        );
+        assert_eq!(
+            session_token(Some(" from-env ".to_string()), Some(&config)).unwrap(),
+            "from-env".to_string()
+        );
+        assert_eq!(
+            session_token(Some(String::new()), Some(&config)).unwrap(),
+            "from-file".to_string()
+        );
+        assert!(session_token(None, None).is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,73 +1,115 @@
-use std::io::{self, Error};
+use clap::Parser;
+use day_01::digits::{decode_digits, DigitPolicy};
+use day_01::report::{print_report, Format};
+use day_01::{parallel, Scanner};
+use std::io::Error;
 
-fn decode(input: &String) -> Result<i32, Error> {
-    let firstnumber = input.find(char::is_numeric);
-    let lastnumber = input.rfind(char::is_numeric);
-    if firstnumber.is_none() || lastnumber.is_none() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("No numbers found: {}", input),
-        ));
-    }
-    let firstnumber = firstnumber.unwrap();
-    let lastnumber = lastnumber.unwrap();
-    let n1 = input[firstnumber..firstnumber + 1].parse::<i32>().unwrap();
-    let n2 = input[lastnumber..lastnumber + 1].parse::<i32>().unwrap();
-    Ok(n1 * 10 + n2)
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 1 Part 1"
+)]
+struct Args {
+    #[arg(
+        short = 'f',
+        long = "input",
+        help = "Input file to use, stdin if not present",
+        default_value = "-"
+    )]
+    file: String,
+    #[arg(
+        long = "parallel",
+        help = "Decode memory mapped chunks of the input on all cores"
+    )]
+    parallel: bool,
+    #[arg(
+        long = "digits",
+        help = "Which characters count as digits",
+        value_enum,
+        default_value_t = DigitPolicy::Ascii
+    )]
+    digits: DigitPolicy,
+    #[arg(
+        long = "report",
+        help = "Explain each line as a table or CSV instead of printing the sum",
+        value_enum
+    )]
+    report: Option<Format>,
 }
 
 fn main() -> Result<(), Error> {
-    let sum = io::stdin()
-        .lines()
-        .map(|line| decode(&line.unwrap()).unwrap())
-        .sum::<i32>();
+    let args = Args::parse();
+    let policy = args.digits;
+    if let Some(format) = args.report {
+        return print_report(&args.file, &Scanner::new(policy.table()), format);
+    }
+    let decode = |line: &str| decode_digits(line, policy);
+    let sum = if args.parallel {
+        parallel::sum_file(&args.file, decode)?
+    } else {
+        day_01::sum_lines(day_01::open_input(&args.file)?, decode)?
+    };
     println!("{}", sum);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use day_01::digits::{decode_digits, DigitPolicy};
+    use std::io::Error;
+
+    fn decode(input: &str) -> Result<u32, Error> {
+        decode_digits(input, DigitPolicy::Ascii)
+    }
 
     #[test]
     fn test_decode() {
         let s1 = "11";
-        assert!(decode(&s1.to_string()).unwrap() == 11);
+        assert!(decode(s1).unwrap() == 11);
     }
 
     #[test]
     fn test_decode2() {
         let s1 = "111";
-        assert!(decode(&s1.to_string()).unwrap() == 11);
+        assert!(decode(s1).unwrap() == 11);
     }
 
     #[test]
     fn test_decode3() {
         let s1 = "pqr3stu8vwx";
-        assert!(decode(&s1.to_string()).unwrap() == 38);
+        assert!(decode(s1).unwrap() == 38);
     }
 
     #[test]
     fn test_decode4() {
         let s1 = "treb7uchet";
-        assert!(decode(&s1.to_string()).unwrap() == 77);
+        assert!(decode(s1).unwrap() == 77);
     }
 
     #[test]
     fn test_decode5() {
         let s1 = "trebuchet";
-        assert!(decode(&s1.to_string()).is_err());
+        assert!(decode(s1).is_err());
+    }
+
+    #[test]
+    fn test_decode_unicode() {
+        let s1 = "٣a²";
+        assert!(decode(s1).is_err());
+        assert_eq!(decode_digits(s1, DigitPolicy::Unicode).unwrap(), 33);
     }
 
     #[test]
     fn test_decode7() {
-        let words = vec!["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"];
+        let words = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"];
         let expect = 142;
         let result = words
             .iter()
             .map(|line| line.to_string())
             .map(|line| decode(&line).unwrap())
-            .sum::<i32>();
+            .sum::<u32>();
         assert_eq!(result, expect);
     }
 }
@@ -1,185 +1,235 @@
-use std::io::{self, Error};
-
-fn decode(input: &String) -> Result<i32, Error> {
-    let mut number_tokens = vec![];
-    let mut i = 0;
-    tracing::debug!("{}", input);
-    while i < input.len() {
-        if char::is_numeric(input.chars().nth(i).unwrap()) {
-            let n = input[i..i + 1].parse::<i32>().unwrap();
-            number_tokens.push(n);
-        } else if input[i..].starts_with("zero") {
-            number_tokens.push(0);
-        } else if input[i..].starts_with("one") {
-            number_tokens.push(1);
-        } else if input[i..].starts_with("two") {
-            number_tokens.push(2);
-        } else if input[i..].starts_with("three") {
-            number_tokens.push(3);
-        } else if input[i..].starts_with("four") {
-            number_tokens.push(4);
-        } else if input[i..].starts_with("five") {
-            number_tokens.push(5);
-        } else if input[i..].starts_with("six") {
-            number_tokens.push(6);
-        } else if input[i..].starts_with("seven") {
-            number_tokens.push(7);
-        } else if input[i..].starts_with("eight") {
-            number_tokens.push(8);
-        } else if input[i..].starts_with("nine") {
-            number_tokens.push(9);
-        }
-        i += 1;
-    }
+use clap::{Parser, ValueEnum};
+use day_01::digits::DigitPolicy;
+use day_01::report::{print_report, Format};
+use day_01::vocabulary::{parse_words, Vocabulary};
+use day_01::{parallel, Scanner};
+use std::io::Error;
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 1 Part 2"
+)]
+struct Args {
+    #[arg(
+        short = 'f',
+        long = "input",
+        help = "Input file to use, stdin if not present",
+        default_value = "-"
+    )]
+    file: String,
+    #[arg(
+        long = "parallel",
+        help = "Decode memory mapped chunks of the input on all cores"
+    )]
+    parallel: bool,
+    #[arg(
+        long = "vocabulary",
+        help = "Language of the spelled out digits",
+        value_enum,
+        default_value_t = VocabularyArg::English
+    )]
+    vocabulary: VocabularyArg,
+    #[arg(
+        long = "digits",
+        help = "Which characters count as digits",
+        value_enum,
+        default_value_t = DigitPolicy::Ascii
+    )]
+    digits: DigitPolicy,
+    #[arg(
+        long = "words",
+        help = "File of 'word value' lines to use instead of a built in vocabulary"
+    )]
+    words: Option<String>,
+    #[arg(
+        long = "report",
+        help = "Explain each line as a table or CSV instead of printing the sum",
+        value_enum
+    )]
+    report: Option<Format>,
+}
 
-    if number_tokens.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("No numbers found: {}", input),
-        ));
-    }
-    tracing::debug!("{:?}", number_tokens);
-    let n1 = number_tokens[0];
-    let n2 = number_tokens[number_tokens.len() - 1];
-    Ok(n1 * 10 + n2)
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum VocabularyArg {
+    English,
+    French,
+    German,
+    Spanish,
+    Roman,
 }
 
 fn main() -> Result<(), Error> {
-    let sum = io::stdin()
-        .lines()
-        .map(|line| decode(&line.unwrap()).unwrap())
-        .sum::<i32>();
+    let args = Args::parse();
+    let digits = args.digits.table();
+    let scanner = match args.words {
+        Some(path) => {
+            let words = parse_words(&std::fs::read_to_string(path)?)?;
+            Scanner::new(digits.into_iter().chain(words))
+        }
+        None => {
+            let vocabulary = match args.vocabulary {
+                VocabularyArg::English => Vocabulary::English,
+                VocabularyArg::French => Vocabulary::French,
+                VocabularyArg::German => Vocabulary::German,
+                VocabularyArg::Spanish => Vocabulary::Spanish,
+                VocabularyArg::Roman => Vocabulary::Roman,
+            };
+            let words = vocabulary
+                .words()
+                .iter()
+                .map(|(word, value)| (word.to_string(), *value));
+            Scanner::new(digits.into_iter().chain(words))
+        }
+    };
+    if let Some(format) = args.report {
+        return print_report(&args.file, &scanner, format);
+    }
+    let decode = |line: &str| day_01::decode(&scanner, line);
+    let sum = if args.parallel {
+        parallel::sum_file(&args.file, decode)?
+    } else {
+        day_01::sum_lines(day_01::open_input(&args.file)?, decode)?
+    };
     println!("{}", sum);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use day_01::{Scanner, DIGITS, ENGLISH};
+    use std::io::Error;
+
+    fn decode(input: &str) -> Result<u32, Error> {
+        let scanner = Scanner::new(DIGITS.iter().chain(ENGLISH.iter()).copied());
+        day_01::decode(&scanner, input)
+    }
 
     #[test]
     fn test_decode() {
         let s1 = "11";
-        assert!(decode(&s1.to_string()).unwrap() == 11);
+        assert!(decode(s1).unwrap() == 11);
     }
 
     #[test]
     fn test_decode2() {
         let s1 = "111";
-        assert!(decode(&s1.to_string()).unwrap() == 11);
+        assert!(decode(s1).unwrap() == 11);
     }
 
     #[test]
     fn test_decode3() {
         let s1 = "pqr3stu8vwx";
-        assert!(decode(&s1.to_string()).unwrap() == 38);
+        assert!(decode(s1).unwrap() == 38);
     }
 
     #[test]
     fn test_decode4() {
         let s1 = "treb7uchet";
-        assert!(decode(&s1.to_string()).unwrap() == 77);
+        assert!(decode(s1).unwrap() == 77);
     }
 
     #[test]
     fn test_decode5() {
         let s1 = "trebuchet";
-        assert!(decode(&s1.to_string()).is_err());
+        assert!(decode(s1).is_err());
     }
 
     #[test]
     fn test_decode6() {
         let s1 = "zero";
-        assert!(decode(&s1.to_string()).unwrap() == 0);
+        assert!(decode(s1).unwrap() == 0);
     }
 
     #[test]
     fn test_decode7() {
         let s1 = "one";
-        assert!(decode(&s1.to_string()).unwrap() == 11);
+        assert!(decode(s1).unwrap() == 11);
     }
 
     #[test]
     fn test_decode8() {
         let s1 = "two";
-        assert!(decode(&s1.to_string()).unwrap() == 22);
+        assert!(decode(s1).unwrap() == 22);
     }
 
     #[test]
     fn test_decode9() {
         let s1 = "three";
-        assert!(decode(&s1.to_string()).unwrap() == 33);
+        assert!(decode(s1).unwrap() == 33);
     }
 
     #[test]
     fn test_decode10() {
         let s1 = "four";
-        assert!(decode(&s1.to_string()).unwrap() == 44);
+        assert!(decode(s1).unwrap() == 44);
     }
 
     #[test]
     fn test_decode11() {
         let s1 = "five";
-        assert!(decode(&s1.to_string()).unwrap() == 55);
+        assert!(decode(s1).unwrap() == 55);
     }
 
     #[test]
     fn test_decode12() {
         let s1 = "six";
-        assert!(decode(&s1.to_string()).unwrap() == 66);
+        assert!(decode(s1).unwrap() == 66);
     }
 
     #[test]
     fn test_decode13() {
         let s1 = "seven";
-        assert!(decode(&s1.to_string()).unwrap() == 77);
+        assert!(decode(s1).unwrap() == 77);
     }
 
     #[test]
     fn test_decode14() {
         let s1 = "eight";
-        assert!(decode(&s1.to_string()).unwrap() == 88);
+        assert!(decode(s1).unwrap() == 88);
     }
 
     #[test]
     fn test_decode15() {
         let s1 = "nine";
-        assert!(decode(&s1.to_string()).unwrap() == 99);
+        assert!(decode(s1).unwrap() == 99);
     }
 
     #[test]
     fn test_decode16() {
         let s1 = "zerozero";
-        assert!(decode(&s1.to_string()).unwrap() == 0);
+        assert!(decode(s1).unwrap() == 0);
     }
 
     #[test]
     fn test_decode17() {
         let s1 = "onetwo";
-        assert!(decode(&s1.to_string()).unwrap() == 12);
+        assert!(decode(s1).unwrap() == 12);
     }
 
     #[test]
     fn test_decode18() {
         let s1 = "twoaone";
-        assert!(decode(&s1.to_string()).unwrap() == 21);
+        assert!(decode(s1).unwrap() == 21);
     }
 
     #[test]
     fn test_decode19() {
         let s1 = "athreebfourc";
-        assert!(decode(&s1.to_string()).unwrap() == 34);
+        assert!(decode(s1).unwrap() == 34);
     }
 
     #[test]
     fn test_decode20() {
         let s1 = "athreebfourc1";
-        assert!(decode(&s1.to_string()).unwrap() == 31);
+        assert!(decode(s1).unwrap() == 31);
     }
 
     #[test]
     fn test_decode21() {
-        let words = vec![
+        let words = [
             "two1nine",
             "eightwothree",
             "abcone2threexyz",
@@ -193,13 +243,13 @@ mod tests {
             .iter()
             .map(|line| line.to_string())
             .map(|line| decode(&line).unwrap())
-            .sum::<i32>();
+            .sum::<u32>();
         assert_eq!(result, expect);
     }
 
     #[test]
     fn test_allowoverlapinwords() {
         let s1 = "ninesevensrzxkzpmgz8kcjxsbdftwoner";
-        assert_eq!(decode(&s1.to_string()).unwrap(), 91);
+        assert_eq!(decode(s1).unwrap(), 91);
     }
 }
@@ -0,0 +1,120 @@
+use std::io::{self, Error};
+
+use crate::DIGITS;
+
+// code point of the zero in each run of ten Unicode decimal digits (Nd) as of
+// Unicode 15, the other nine follow it in order
+const DECIMAL_ZEROS: [u32; 68] = [
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66,
+    0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60, 0x16AC0,
+    0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E950,
+    0x1FBF0,
+];
+
+// Which characters count as digits. Numeric characters that are not decimal
+// digits, such as ² or ½, never count.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum DigitPolicy {
+    Ascii,
+    Unicode,
+}
+
+impl DigitPolicy {
+    pub fn value(&self, c: char) -> Option<u32> {
+        match self {
+            DigitPolicy::Ascii => c.to_digit(10),
+            DigitPolicy::Unicode => {
+                let c = c as u32;
+                DECIMAL_ZEROS
+                    .iter()
+                    .find(|zero| (**zero..**zero + 10).contains(&c))
+                    .map(|zero| c - zero)
+            }
+        }
+    }
+
+    // every counted digit as a word for the scanner
+    pub fn table(&self) -> Vec<(String, u32)> {
+        match self {
+            DigitPolicy::Ascii => DIGITS
+                .iter()
+                .map(|(word, value)| (word.to_string(), *value))
+                .collect(),
+            DigitPolicy::Unicode => DECIMAL_ZEROS
+                .iter()
+                .flat_map(|zero| (0..10).map(move |value| (zero + value, value)))
+                .filter_map(|(c, value)| char::from_u32(c).map(|c| (c.to_string(), value)))
+                .collect(),
+        }
+    }
+}
+
+//
+// functions
+//
+
+pub fn decode_digits(input: &str, policy: DigitPolicy) -> Result<u32, Error> {
+    let mut digits = input.chars().filter_map(|c| policy.value(c));
+    match digits.next() {
+        Some(first) => {
+            let last = digits.next_back().unwrap_or(first);
+            Ok(first * 10 + last)
+        }
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("No numbers found: {}", input),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, Scanner};
+
+    #[test]
+    fn test_value() {
+        assert_eq!(DigitPolicy::Ascii.value('7'), Some(7));
+        assert_eq!(DigitPolicy::Ascii.value('٣'), None);
+        assert_eq!(DigitPolicy::Unicode.value('٣'), Some(3));
+        assert_eq!(DigitPolicy::Unicode.value('९'), Some(9));
+        assert_eq!(DigitPolicy::Unicode.value('７'), Some(7));
+        assert_eq!(DigitPolicy::Unicode.value('²'), None);
+        assert_eq!(DigitPolicy::Unicode.value('½'), None);
+        assert_eq!(DigitPolicy::Unicode.value('x'), None);
+    }
+
+    #[test]
+    fn test_table_matches_value() {
+        for policy in [DigitPolicy::Ascii, DigitPolicy::Unicode] {
+            for (word, value) in policy.table() {
+                let c = word.chars().next().unwrap();
+                assert_eq!(word.chars().count(), 1);
+                assert_eq!(policy.value(c), Some(value));
+                assert!(c.is_numeric());
+            }
+        }
+        assert_eq!(DigitPolicy::Unicode.table().len(), 680);
+    }
+
+    #[test]
+    fn test_decode_digits() {
+        assert_eq!(decode_digits("a٣b4", DigitPolicy::Unicode).unwrap(), 34);
+        assert_eq!(decode_digits("a٣b4", DigitPolicy::Ascii).unwrap(), 44);
+        assert!(decode_digits("x²y", DigitPolicy::Unicode).is_err());
+        assert!(decode_digits("٣", DigitPolicy::Ascii).is_err());
+        assert_eq!(decode_digits("é1ü", DigitPolicy::Ascii).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_scanner_unicode() {
+        let scanner = Scanner::new(DigitPolicy::Unicode.table());
+        assert_eq!(decode(&scanner, "٣x²y९").unwrap(), 39);
+        let first = scanner.first("é٣").unwrap();
+        assert_eq!(first.start, 2);
+        assert_eq!(first.end, 4);
+    }
+}
@@ -0,0 +1,313 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Error};
+
+pub mod digits;
+pub mod parallel;
+pub mod report;
+pub mod vocabulary;
+
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const ENGLISH: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+const NONE: u32 = u32::MAX;
+
+// Aho-Corasick automaton over bytes with a dense transition table
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    // (length, value) of every word ending in this state, longest first
+    output: Vec<Vec<(usize, u32)>>,
+}
+
+impl Automaton {
+    fn new(words: &[(Vec<u8>, u32)]) -> Self {
+        let mut next = vec![[NONE; 256]];
+        let mut output: Vec<Vec<(usize, u32)>> = vec![vec![]];
+        for (word, value) in words.iter().filter(|(word, _)| !word.is_empty()) {
+            let mut state = 0;
+            for b in word.iter() {
+                if next[state][*b as usize] == NONE {
+                    next.push([NONE; 256]);
+                    output.push(vec![]);
+                    next[state][*b as usize] = (next.len() - 1) as u32;
+                }
+                state = next[state][*b as usize] as usize;
+            }
+            if output[state].is_empty() {
+                output[state].push((word.len(), *value));
+            }
+        }
+
+        let mut fail = vec![0; next.len()];
+        let mut queue = VecDeque::new();
+        for target in next[0].iter_mut() {
+            match *target {
+                NONE => *target = 0,
+                state => queue.push_back(state as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let inherited = output[fail[state]].clone();
+            output[state].extend(inherited);
+            output[state].sort_by_key(|(len, _)| Reverse(*len));
+            let fallback = next[fail[state]];
+            for (b, target) in next[state].iter_mut().enumerate() {
+                match *target {
+                    NONE => *target = fallback[b],
+                    child => {
+                        fail[child as usize] = fallback[b] as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Automaton { next, output }
+    }
+
+    fn step(&self, state: usize, b: u8) -> usize {
+        self.next[state][b as usize] as usize
+    }
+}
+
+// Finds number words in a line, allowing matches to overlap ("twone" holds
+// both two and one). When several words start at the same position the
+// longest is first, and when several end at the same position the longest
+// is last.
+pub struct Scanner {
+    forward: Automaton,
+    reverse: Automaton,
+    longest: usize,
+}
+
+impl Scanner {
+    pub fn new<S: AsRef<str>>(table: impl IntoIterator<Item = (S, u32)>) -> Self {
+        let words = table
+            .into_iter()
+            .map(|(word, value)| (word.as_ref().as_bytes().to_vec(), value))
+            .collect::<Vec<(Vec<u8>, u32)>>();
+        let reversed = words
+            .iter()
+            .map(|(word, value)| (word.iter().rev().copied().collect(), *value))
+            .collect::<Vec<(Vec<u8>, u32)>>();
+        Scanner {
+            forward: Automaton::new(&words),
+            reverse: Automaton::new(&reversed),
+            longest: words.iter().map(|(word, _)| word.len()).max().unwrap_or(0),
+        }
+    }
+
+    // every match in the text ordered by start position
+    pub fn matches(&self, text: &str) -> Vec<Match> {
+        let mut found = vec![];
+        let mut state = 0;
+        for (i, b) in text.bytes().enumerate() {
+            state = self.forward.step(state, b);
+            for (len, value) in self.forward.output[state].iter() {
+                found.push(Match {
+                    start: i + 1 - len,
+                    end: i + 1,
+                    value: *value,
+                });
+            }
+        }
+        found.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        found
+    }
+
+    // earliest starting match in `bytes`, the longest when several start together
+    fn earliest(&self, automaton: &Automaton, bytes: impl Iterator<Item = u8>) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+        for (i, b) in bytes.enumerate() {
+            // no match ending later can start at or before the best one
+            if let Some(found) = best {
+                if i + 1 > found.start + self.longest {
+                    break;
+                }
+            }
+            state = automaton.step(state, b);
+            for (len, value) in automaton.output[state].iter() {
+                let start = i + 1 - len;
+                let better = best.is_none_or(|found| {
+                    start < found.start || (start == found.start && i + 1 > found.end)
+                });
+                if better {
+                    best = Some(Match {
+                        start,
+                        end: i + 1,
+                        value: *value,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    pub fn first(&self, text: &str) -> Option<Match> {
+        self.earliest(&self.forward, text.bytes())
+    }
+
+    // the earliest match in the reversed text is the match ending last, so a
+    // longer word wins over a shorter one it ends with ("VIII" over "I")
+    pub fn last(&self, text: &str) -> Option<Match> {
+        self.earliest(&self.reverse, text.bytes().rev())
+            .map(|found| Match {
+                start: text.len() - found.end,
+                end: text.len() - found.start,
+                value: found.value,
+            })
+    }
+}
+
+//
+// functions
+//
+
+pub fn decode(scanner: &Scanner, input: &str) -> Result<u32, Error> {
+    tracing::debug!("{}", input);
+    match (scanner.first(input), scanner.last(input)) {
+        (Some(first), Some(last)) => {
+            tracing::debug!("{:?} {:?}", first, last);
+            Ok(first.value * 10 + last.value)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("No numbers found: {}", input),
+        )),
+    }
+}
+
+pub fn open_input(file: &str) -> Result<Box<dyn BufRead>, Error> {
+    if file == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(file)?)))
+    }
+}
+
+pub fn sum_lines<F>(input: impl BufRead, decode: F) -> Result<u64, Error>
+where
+    F: Fn(&str) -> Result<u32, Error>,
+{
+    let mut sum = 0;
+    for line in input.lines() {
+        sum += u64::from(decode(&line?)?);
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> Scanner {
+        Scanner::new(DIGITS.iter().chain(ENGLISH.iter()).copied())
+    }
+
+    #[test]
+    fn test_first_last() {
+        let scanner = english();
+        let first = scanner.first("xtwone3four").unwrap();
+        assert_eq!(
+            first,
+            Match {
+                start: 1,
+                end: 4,
+                value: 2
+            }
+        );
+        let last = scanner.last("xtwone3four").unwrap();
+        assert_eq!(
+            last,
+            Match {
+                start: 7,
+                end: 11,
+                value: 4
+            }
+        );
+        assert_eq!(scanner.first("trebuchet"), None);
+        assert_eq!(scanner.last("trebuchet"), None);
+        assert_eq!(scanner.first(""), None);
+    }
+
+    #[test]
+    fn test_overlap() {
+        let scanner = english();
+        assert_eq!(scanner.first("twone").unwrap().value, 2);
+        assert_eq!(scanner.last("twone").unwrap().value, 1);
+        assert_eq!(scanner.first("eightwo").unwrap().value, 8);
+        assert_eq!(scanner.last("eightwo").unwrap().value, 2);
+        let values = scanner
+            .matches("oneightwone")
+            .iter()
+            .map(|m| m.value)
+            .collect::<Vec<u32>>();
+        assert_eq!(values, vec![1, 8, 2, 1]);
+    }
+
+    #[test]
+    fn test_longest_at_same_start() {
+        let scanner = Scanner::new([("a", 1), ("abcd", 4), ("bc", 2)]);
+        assert_eq!(scanner.first("xabcd").unwrap().value, 4);
+        assert_eq!(scanner.last("abcdx").unwrap().value, 4);
+        assert_eq!(scanner.last("abcx").unwrap().value, 2);
+        assert_eq!(
+            scanner.matches("abcd"),
+            vec![
+                Match {
+                    start: 0,
+                    end: 4,
+                    value: 4
+                },
+                Match {
+                    start: 0,
+                    end: 1,
+                    value: 1
+                },
+                Match {
+                    start: 1,
+                    end: 3,
+                    value: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pluggable_table() {
+        let scanner = Scanner::new([("uno", 1), ("dos", 2)]);
+        assert_eq!(decode(&scanner, "xunoydos2").unwrap(), 12);
+        assert!(decode(&scanner, "one2").is_err());
+    }
+}
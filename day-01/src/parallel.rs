@@ -0,0 +1,125 @@
+use std::fs::File;
+use std::io::{self, Error, Read};
+
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+const CHUNK_SIZE: usize = 1 << 20;
+
+// split at the first newline after every `size` bytes so no line straddles two chunks
+fn chunks(data: &[u8], size: usize) -> Vec<&[u8]> {
+    let mut chunks = vec![];
+    let mut rest = data;
+    while rest.len() > size {
+        match rest[size..].iter().position(|b| *b == b'\n') {
+            Some(n) => {
+                let (chunk, tail) = rest.split_at(size + n + 1);
+                chunks.push(chunk);
+                rest = tail;
+            }
+            None => break,
+        }
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+fn sum_chunk<F>(chunk: &[u8], decode: &F) -> Result<u64, Error>
+where
+    F: Fn(&str) -> Result<u32, Error>,
+{
+    let mut sum = 0;
+    for line in chunk.split_inclusive(|b| *b == b'\n') {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line =
+            std::str::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        sum += u64::from(decode(line)?);
+    }
+    Ok(sum)
+}
+
+//
+// functions
+//
+
+pub fn sum_bytes<F>(data: &[u8], decode: F) -> Result<u64, Error>
+where
+    F: Fn(&str) -> Result<u32, Error> + Sync,
+{
+    chunks(data, CHUNK_SIZE)
+        .par_iter()
+        .map(|chunk| sum_chunk(chunk, &decode))
+        .try_reduce(|| 0, |a, b| Ok(a + b))
+}
+
+// memory maps the file, or reads all of stdin for "-"
+pub fn sum_file<F>(path: &str, decode: F) -> Result<u64, Error>
+where
+    F: Fn(&str) -> Result<u32, Error> + Sync,
+{
+    if path == "-" {
+        let mut data = vec![];
+        io::stdin().read_to_end(&mut data)?;
+        return sum_bytes(&data, decode);
+    }
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(0);
+    }
+    // safety: the file is only read, and is not expected to change while mapped
+    let data = unsafe { Mmap::map(&file)? };
+    sum_bytes(&data, decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, Scanner, DIGITS, ENGLISH};
+
+    #[test]
+    fn test_chunks() {
+        let data = b"ab\ncd\nef\ngh";
+        assert_eq!(chunks(data, 1), vec![&b"ab\n"[..], b"cd\n", b"ef\n", b"gh"]);
+        assert_eq!(chunks(data, 4), vec![&b"ab\ncd\n"[..], b"ef\ngh"]);
+        assert_eq!(chunks(data, 100), vec![&data[..]]);
+        assert!(chunks(b"", 4).is_empty());
+    }
+
+    #[test]
+    fn test_sum_bytes_matches_sequential() {
+        let scanner = Scanner::new(DIGITS.iter().chain(ENGLISH.iter()).copied());
+        let words = [
+            "two1nine",
+            "eightwothree",
+            "abcone2threexyz",
+            "xtwone3four",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ];
+        let mut data = String::new();
+        for n in 0..200_000 {
+            data.push_str(words[n % words.len()]);
+            data.push_str(if n % 3 == 0 { "\r\n" } else { "\n" });
+        }
+        let sequential = data
+            .lines()
+            .map(|line| u64::from(decode(&scanner, line).unwrap()))
+            .sum::<u64>();
+        let parallel = sum_bytes(data.as_bytes(), |line| decode(&scanner, line)).unwrap();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_sum_bytes_error() {
+        let scanner = Scanner::new(DIGITS);
+        assert!(sum_bytes(b"1\nabc\n2\n", |line| decode(&scanner, line)).is_err());
+        assert_eq!(
+            sum_bytes(b"1\n2", |line| decode(&scanner, line)).unwrap(),
+            33
+        );
+    }
+}
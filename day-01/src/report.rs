@@ -0,0 +1,294 @@
+use std::fmt::Write;
+use std::io::{BufRead, Error};
+
+use crate::{open_input, Match, Scanner};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Numeric,
+    Spelled,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Numeric => "numeric",
+            Kind::Spelled => "spelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub text: String,
+    // 1-based character column of the first character
+    pub column: usize,
+    pub kind: Kind,
+    pub value: u32,
+}
+
+impl Token {
+    fn new(input: &str, found: Match) -> Self {
+        let text = input[found.start..found.end].to_string();
+        let kind = if text.chars().count() == 1 && text.chars().all(char::is_numeric) {
+            Kind::Numeric
+        } else {
+            Kind::Spelled
+        };
+        Token {
+            text,
+            column: input[..found.start].chars().count() + 1,
+            kind,
+            value: found.value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineReport {
+    pub line: usize,
+    pub input: String,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+}
+
+impl LineReport {
+    pub fn value(&self) -> Option<u32> {
+        match (&self.first, &self.last) {
+            (Some(first), Some(last)) => Some(first.value * 10 + last.value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub lines: Vec<LineReport>,
+}
+
+impl Report {
+    pub fn sum(&self) -> u64 {
+        self.lines
+            .iter()
+            .filter_map(|line| line.value())
+            .map(u64::from)
+            .sum()
+    }
+
+    pub fn failed(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .filter(|line| line.value().is_none())
+            .map(|line| line.line)
+            .collect()
+    }
+
+    pub fn summary(&self) -> String {
+        let failed = self.failed();
+        let mut out = format!(
+            "decoded {} of {} lines, sum {}\n",
+            self.lines.len() - failed.len(),
+            self.lines.len(),
+            self.sum()
+        );
+        if !failed.is_empty() {
+            let failed = failed
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>();
+            writeln!(out, "no digits found on lines: {}", failed.join(", ")).unwrap();
+        }
+        out
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![[
+            "line".to_string(),
+            "first".to_string(),
+            "last".to_string(),
+            "value".to_string(),
+            "input".to_string(),
+        ]];
+        for line in self.lines.iter() {
+            let token = |token: &Option<Token>| match token {
+                Some(token) => format!("{}@{} {}", token.text, token.column, token.kind.name()),
+                None => "-".to_string(),
+            };
+            rows.push([
+                line.line.to_string(),
+                token(&line.first),
+                token(&line.last),
+                line.value()
+                    .map_or("error".to_string(), |value| value.to_string()),
+                line.input.clone(),
+            ]);
+        }
+        let mut widths = [0; 5];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut out = String::new();
+        for row in rows.iter() {
+            let cells = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<String>>();
+            writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+        }
+        out.push_str(&self.summary());
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "line,first,first_column,first_kind,last,last_column,last_kind,value,input\n",
+        );
+        for line in self.lines.iter() {
+            let token = |token: &Option<Token>| match token {
+                Some(token) => format!(
+                    "{},{},{}",
+                    csv_field(&token.text),
+                    token.column,
+                    token.kind.name()
+                ),
+                None => ",,".to_string(),
+            };
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                line.line,
+                token(&line.first),
+                token(&line.last),
+                line.value()
+                    .map_or(String::new(), |value| value.to_string()),
+                csv_field(&line.input)
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+//
+// functions
+//
+
+pub fn explain(scanner: &Scanner, line: usize, input: &str) -> LineReport {
+    LineReport {
+        line,
+        input: input.to_string(),
+        first: scanner.first(input).map(|found| Token::new(input, found)),
+        last: scanner.last(input).map(|found| Token::new(input, found)),
+    }
+}
+
+// the CSV summary goes to stderr so stdout stays plain CSV
+pub fn print_report(file: &str, scanner: &Scanner, format: Format) -> Result<(), Error> {
+    let lines = open_input(file)?
+        .lines()
+        .collect::<Result<Vec<String>, Error>>()?;
+    let report = report(scanner, lines);
+    match format {
+        Format::Table => print!("{}", report.to_table()),
+        Format::Csv => {
+            print!("{}", report.to_csv());
+            eprint!("{}", report.summary());
+        }
+    }
+    Ok(())
+}
+
+pub fn report<S: AsRef<str>>(scanner: &Scanner, lines: impl IntoIterator<Item = S>) -> Report {
+    Report {
+        lines: lines
+            .into_iter()
+            .enumerate()
+            .map(|(n, input)| explain(scanner, n + 1, input.as_ref()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DIGITS, ENGLISH};
+
+    fn english() -> Scanner {
+        Scanner::new(DIGITS.iter().chain(ENGLISH.iter()).copied())
+    }
+
+    #[test]
+    fn test_explain() {
+        let line = explain(&english(), 3, "xtwone3four");
+        assert_eq!(
+            line.first,
+            Some(Token {
+                text: "two".to_string(),
+                column: 2,
+                kind: Kind::Spelled,
+                value: 2
+            })
+        );
+        assert_eq!(line.last.as_ref().unwrap().column, 8);
+        assert_eq!(line.value(), Some(24));
+        let line = explain(&english(), 1, "é7");
+        assert_eq!(line.first.as_ref().unwrap().column, 2);
+        assert_eq!(line.first.as_ref().unwrap().kind, Kind::Numeric);
+    }
+
+    #[test]
+    fn test_report_failures() {
+        let report = report(&english(), ["two1nine", "trebuchet", "7pqrstsixteen"]);
+        assert_eq!(report.sum(), 29 + 76);
+        assert_eq!(report.failed(), vec![2]);
+        assert_eq!(
+            report.summary(),
+            "decoded 2 of 3 lines, sum 105\nno digits found on lines: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_to_table() {
+        let report = report(&english(), ["two1nine", "abc"]);
+        let expect = [
+            "line  first          last            value  input",
+            "1     two@1 spelled  nine@5 spelled  29     two1nine",
+            "2     -              -               error  abc",
+            "decoded 1 of 2 lines, sum 29",
+            "no digits found on lines: 2",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(report.to_table(), expect);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let report = report(&english(), ["a1,b\"2", "abc"]);
+        let expect = [
+            "line,first,first_column,first_kind,last,last_column,last_kind,value,input",
+            "1,1,2,numeric,2,6,numeric,12,\"a1,b\"\"2\"",
+            "2,,,,,,,,abc",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(report.to_csv(), expect);
+    }
+}
@@ -0,0 +1,166 @@
+use std::io::{self, Error};
+
+use crate::ENGLISH;
+
+pub const FRENCH: [(&str, u32); 10] = [
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+pub const GERMAN: [(&str, u32); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+pub const SPANISH: [(&str, u32); 10] = [
+    ("cero", 0),
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+// upper case only, lower case i and v are too common in ordinary words
+pub const ROMAN: [(&str, u32); 9] = [
+    ("I", 1),
+    ("II", 2),
+    ("III", 3),
+    ("IV", 4),
+    ("V", 5),
+    ("VI", 6),
+    ("VII", 7),
+    ("VIII", 8),
+    ("IX", 9),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Vocabulary {
+    English,
+    French,
+    German,
+    Spanish,
+    Roman,
+}
+
+impl Vocabulary {
+    pub fn words(&self) -> &'static [(&'static str, u32)] {
+        match self {
+            Vocabulary::English => &ENGLISH,
+            Vocabulary::French => &FRENCH,
+            Vocabulary::German => &GERMAN,
+            Vocabulary::Spanish => &SPANISH,
+            Vocabulary::Roman => &ROMAN,
+        }
+    }
+}
+
+//
+// functions
+//
+
+// one "word value" pair per line, blank lines and lines starting with # ignored
+pub fn parse_words(input: &str) -> Result<Vec<(String, u32)>, Error> {
+    let mut words: Vec<(String, u32)> = vec![];
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}: {}", n + 1, reason, line),
+            )
+        };
+        let (word, value) = line
+            .rsplit_once(char::is_whitespace)
+            .ok_or_else(|| invalid("expected 'word value'"))?;
+        let word = word.trim();
+        let value = value
+            .parse::<u32>()
+            .ok()
+            .filter(|value| *value <= 9)
+            .ok_or_else(|| invalid("value must be a digit 0-9"))?;
+        if words.iter().any(|(known, _)| known == word) {
+            return Err(invalid("duplicate word"));
+        }
+        words.push((word.to_string(), value));
+    }
+    if words.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "word list is empty",
+        ));
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, Scanner, DIGITS};
+
+    fn scanner(vocabulary: Vocabulary) -> Scanner {
+        Scanner::new(DIGITS.iter().chain(vocabulary.words().iter()).copied())
+    }
+
+    #[test]
+    fn test_french() {
+        let scanner = scanner(Vocabulary::French);
+        assert_eq!(decode(&scanner, "xdeuxtroisept").unwrap(), 27);
+        assert_eq!(decode(&scanner, "zéro4").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_german() {
+        let scanner = scanner(Vocabulary::German);
+        assert_eq!(decode(&scanner, "fünfzweins").unwrap(), 51);
+    }
+
+    #[test]
+    fn test_spanish() {
+        let scanner = scanner(Vocabulary::Spanish);
+        assert_eq!(decode(&scanner, "unodosiete").unwrap(), 17);
+    }
+
+    #[test]
+    fn test_roman() {
+        let scanner = scanner(Vocabulary::Roman);
+        assert_eq!(decode(&scanner, "xVIIIy").unwrap(), 88);
+        assert_eq!(decode(&scanner, "IV7VII").unwrap(), 47);
+        assert_eq!(decode(&scanner, "xIII").unwrap(), 33);
+        assert_eq!(decode(&scanner, "IXa3").unwrap(), 93);
+        assert!(decode(&scanner, "vii").is_err());
+    }
+
+    #[test]
+    fn test_parse_words() {
+        let words = parse_words("# numbers\nuno 1\n\ndos  2\n").unwrap();
+        assert_eq!(words, vec![("uno".to_string(), 1), ("dos".to_string(), 2)]);
+        assert!(parse_words("uno").is_err());
+        assert!(parse_words("uno 10").is_err());
+        assert!(parse_words("uno 1\nuno 2").is_err());
+        assert!(parse_words("# nothing\n").is_err());
+    }
+}
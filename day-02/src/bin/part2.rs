@@ -1,84 +1,62 @@
 use anyhow::Error;
+use clap::{Parser, ValueEnum};
+use day_02::options::BagArgs;
+use day_02::{parse_games, summarize, CubeSet};
 use std::io;
 use tracing::Level;
 
-#[derive(Debug)]
-struct CubeGame {
-    red: u32,
-    green: u32,
-    blue: u32,
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 2 Part 2"
+)]
+struct Args {
+    #[command(flatten)]
+    bag: BagArgs,
+    #[arg(
+        long = "format",
+        help = "Print the answer, or every parsed game as JSON",
+        value_enum,
+        default_value_t = OutputFormat::Text
+    )]
+    format: OutputFormat,
 }
 
-impl CubeGame {
-    fn parse(input: &str) -> Result<CubeGame, Error> {
-        let mut red = 0;
-        let mut green = 0;
-        let mut blue = 0;
-
-        let mut value = 0;
-        for colorpart in input.split(',').map(|part| part.trim()) {
-            for part in colorpart.split(char::is_whitespace) {
-                tracing::debug!("{}", part);
-                if value == 0 {
-                    value = part.parse::<u32>()?;
-                } else {
-                    match part.trim() {
-                        "red" => red = value,
-                        "green" => green = value,
-                        "blue" => blue = value,
-                        _ => return Err(anyhow::anyhow!("Invalid color")),
-                    }
-                    value = 0;
-                }
-            }
-        }
-        Ok(CubeGame { red, green, blue })
-    }
-
-    fn max(&mut self, cube: &CubeGame) {
-        self.red = self.red.max(cube.red);
-        self.green = self.green.max(cube.green);
-        self.blue = self.blue.max(cube.blue);
-    }
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    Text,
+    Json,
 }
 
-fn sum_minimum_power_games(line: Vec<String>) -> Result<u32, Error> {
-    let sum = line
-        .iter()
-        .map(|line| {
-            let mut minimum_cube = CubeGame {
-                red: u32::MIN,
-                green: u32::MIN,
-                blue: u32::MIN,
-            };
-
-            for token in line.split(':') {
-                tracing::debug!("{}", token);
-                if !token.contains("Game") {
-                    tracing::debug!("Set {}", token);
-                    let set_list = token.split(';').map(|set| set.trim());
-                    for set in set_list {
-                        tracing::debug!("{}", set);
-                        let cube = CubeGame::parse(set).unwrap();
-                        minimum_cube.max(&cube);
-                        tracing::debug!("{:?}", minimum_cube);
-                    }
-                }
-            }
-            minimum_cube.red * minimum_cube.green * minimum_cube.blue
-        })
-        .sum::<u32>();
-    Ok(sum)
+fn sum_minimum_power_games(lines: Vec<String>, bag: &CubeSet) -> Result<u64, Error> {
+    let summary = summarize(parse_games(&lines)?, bag);
+    for game in summary.games.iter() {
+        tracing::debug!("Game {} {}", game.game.id, game.minimum_bag);
+    }
+    Ok(summary.power_sum)
 }
 
 fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_writer(io::stderr)
+        .init();
+    let args = Args::parse();
+    let bag = args.bag.bag()?;
+    tracing::info!("Bag {}", bag);
     let lines = io::stdin()
         .lines()
         .map(|line| line.unwrap())
         .collect::<Vec<String>>();
 
-    let sum = sum_minimum_power_games(lines)?;
+    if let OutputFormat::Json = args.format {
+        let summary = summarize(parse_games(&lines)?, &bag);
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+    let sum = sum_minimum_power_games(lines, &bag)?;
     println!("{}", sum);
     Ok(())
 }
@@ -87,21 +65,13 @@ fn main() -> Result<(), Error> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse() {
-        let game = CubeGame::parse("1 red, 2 green, 3 blue").unwrap();
-        assert_eq!(game.red, 1);
-        assert_eq!(game.green, 2);
-        assert_eq!(game.blue, 3);
-    }
-
     #[test]
     fn test_sum_possible_games() {
         let lines = vec![
             String::from("Game 1: 1 red, 2 green, 3 blue; 2 red, 3 green, 4 blue"),
             String::from("Game 2: 2 red, 3 green, 2 blue; 1 red, 2 green, 3 blue"),
         ];
-        let sum = sum_minimum_power_games(lines).unwrap();
+        let sum = sum_minimum_power_games(lines, &CubeSet::default()).unwrap();
         assert_eq!(sum, 42);
     }
 
@@ -118,7 +88,7 @@ mod tests {
             ),
             String::from("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
         ];
-        let sum = sum_minimum_power_games(lines).unwrap();
+        let sum = sum_minimum_power_games(lines, &CubeSet::default()).unwrap();
         assert_eq!(sum, 2286);
     }
 }
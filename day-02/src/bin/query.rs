@@ -0,0 +1,87 @@
+use std::io::Read;
+
+use anyhow::Error;
+use clap::{Parser, Subcommand};
+use day_02::{parse_games, query, CubeSet};
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 2 bag queries"
+)]
+struct Args {
+    #[arg(
+        short = 'f',
+        long = "input",
+        help = "Input file to use, stdin if not present",
+        default_value = "-"
+    )]
+    file: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// The smallest bag that makes every game possible
+    MinimumBag,
+    /// The bag of at most BUDGET cubes that makes the most games possible
+    Budget { budget: u64 },
+    /// For each impossible game, the first draw and color that does not fit
+    Violations {
+        #[arg(long, default_value = "12 red, 13 green, 14 blue")]
+        bag: CubeSet,
+    },
+}
+
+fn read_lines(file: &str) -> Result<Vec<String>, Error> {
+    let mut buf = String::new();
+    if file == "-" {
+        std::io::stdin().read_to_string(&mut buf)?;
+    } else {
+        buf = std::fs::read_to_string(file)?;
+    }
+    Ok(buf.lines().map(|line| line.to_string()).collect())
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let games = parse_games(&read_lines(&args.file)?)?;
+    match args.command {
+        Command::MinimumBag => {
+            let bag = query::minimum_bag(&games);
+            println!("{} ({} cubes)", bag, bag.total());
+        }
+        Command::Budget { budget } => {
+            let plan = query::best_bag_for_budget(&games, budget);
+            let ids = plan
+                .games
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>();
+            println!(
+                "{} of {} games possible with {} ({} cubes)",
+                plan.games.len(),
+                games.len(),
+                plan.bag,
+                plan.bag.total()
+            );
+            println!("games: {}", ids.join(" "));
+        }
+        Command::Violations { bag } => {
+            for violation in query::violations(&games, &bag) {
+                println!(
+                    "Game {}: draw {} has {} {}, bag holds {}",
+                    violation.game,
+                    violation.draw,
+                    violation.count,
+                    violation.color,
+                    violation.available
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,352 @@
+use anyhow::Error;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod options;
+pub mod query;
+
+// A multiset of cubes keyed by color. Colors with no cubes are not stored, so
+// "0 red" and an absent red compare equal.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(transparent)]
+pub struct CubeSet {
+    cubes: BTreeMap<String, u32>,
+}
+
+impl CubeSet {
+    // "<count> <color>, <count> <color>, ..." with each color at most once
+    pub fn parse(input: &str) -> Result<CubeSet, Error> {
+        let mut set = CubeSet::default();
+        let mut seen: Vec<&str> = vec![];
+        for part in input.split(',').map(|part| part.trim()) {
+            let (count, color) = part
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| anyhow::anyhow!("expected '<count> <color>': '{}'", part))?;
+            let count = count
+                .parse::<u32>()
+                .map_err(|_| anyhow::anyhow!("invalid cube count: '{}'", count))?;
+            let color = color.trim();
+            if !color.chars().all(char::is_alphabetic) {
+                return Err(anyhow::anyhow!("invalid color: '{}'", color));
+            }
+            if seen.contains(&color) {
+                return Err(anyhow::anyhow!("duplicate color in draw: '{}'", color));
+            }
+            seen.push(color);
+            set.insert(color, count);
+        }
+        Ok(set)
+    }
+
+    pub fn insert(&mut self, color: &str, count: u32) {
+        if count == 0 {
+            self.cubes.remove(color);
+        } else {
+            self.cubes.insert(color.to_string(), count);
+        }
+    }
+
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cubes.is_empty()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.cubes.values().map(|count| u64::from(*count)).sum()
+    }
+
+    // true when every cube of `other` is also in this set
+    pub fn contains(&self, other: &CubeSet) -> bool {
+        other.iter().all(|(color, count)| count <= self.get(color))
+    }
+
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        bag.contains(self)
+    }
+
+    pub fn max(&mut self, other: &CubeSet) {
+        for (color, count) in other.iter() {
+            if count > self.get(color) {
+                self.insert(color, count);
+            }
+        }
+    }
+
+    // product of the counts, an empty set has no power
+    pub fn power(&self) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+        self.cubes.values().map(|count| u64::from(*count)).product()
+    }
+}
+
+impl FromStr for CubeSet {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        CubeSet::parse(input)
+    }
+}
+
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts = self
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect::<Vec<String>>();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameRecord {
+    pub id: u32,
+    pub draws: Vec<CubeSet>,
+}
+
+impl GameRecord {
+    // "Game <id>: <draw>; <draw>; ..."
+    pub fn parse(line: &str) -> Result<GameRecord, Error> {
+        let (game, draws) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("expected 'Game <id>: <draws>': {}", line))?;
+        let id = game
+            .trim()
+            .strip_prefix("Game")
+            .filter(|id| id.starts_with(char::is_whitespace))
+            .ok_or_else(|| anyhow::anyhow!("expected 'Game <id>': {}", game))?
+            .trim();
+        let id = id
+            .parse::<u32>()
+            .map_err(|_| anyhow::anyhow!("invalid game id: '{}'", id))?;
+        let draws = draws
+            .split(';')
+            .map(|draw| CubeSet::parse(draw.trim()))
+            .collect::<Result<Vec<CubeSet>, Error>>()
+            .map_err(|e| anyhow::anyhow!("game {}: {}", id, e))?;
+        Ok(GameRecord { id, draws })
+    }
+
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.draws.iter().all(|draw| draw.is_possible(bag))
+    }
+
+    // the fewest cubes of each color the bag could have held
+    pub fn minimum_bag(&self) -> CubeSet {
+        let mut bag = CubeSet::default();
+        for draw in self.draws.iter() {
+            bag.max(draw);
+        }
+        bag
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameSummary {
+    #[serde(flatten)]
+    pub game: GameRecord,
+    pub minimum_bag: CubeSet,
+    pub power: u64,
+    pub possible: bool,
+}
+
+// everything both parts compute, for tools that consume the JSON output
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub bag: CubeSet,
+    pub games: Vec<GameSummary>,
+    pub possible_sum: u64,
+    pub power_sum: u64,
+}
+
+//
+// functions
+//
+
+pub fn summarize(games: Vec<GameRecord>, bag: &CubeSet) -> Summary {
+    let games = games
+        .into_iter()
+        .map(|game| {
+            let minimum_bag = game.minimum_bag();
+            GameSummary {
+                power: minimum_bag.power(),
+                possible: game.is_possible(bag),
+                minimum_bag,
+                game,
+            }
+        })
+        .collect::<Vec<GameSummary>>();
+    Summary {
+        bag: bag.clone(),
+        possible_sum: games
+            .iter()
+            .filter(|summary| summary.possible)
+            .map(|summary| u64::from(summary.game.id))
+            .sum(),
+        power_sum: games.iter().map(|summary| summary.power).sum(),
+        games,
+    }
+}
+
+// A bag configuration file, either the draw grammar on one line or one
+// "<count> <color>" per line. Blank lines and lines starting with # are skipped.
+pub fn parse_bag(input: &str) -> Result<CubeSet, Error> {
+    let parts = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<&str>>();
+    if parts.is_empty() {
+        return Err(anyhow::anyhow!("bag configuration is empty"));
+    }
+    CubeSet::parse(&parts.join(", "))
+}
+
+// one game per line, blank lines are skipped
+pub fn parse_games(lines: &[String]) -> Result<Vec<GameRecord>, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            GameRecord::parse(line).map_err(|e| anyhow::anyhow!("line {}: {}", n + 1, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cubes(input: &str) -> CubeSet {
+        CubeSet::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cube_set() {
+        let set = cubes("1 red, 2 green, 3 blue");
+        assert_eq!(set.get("red"), 1);
+        assert_eq!(set.get("green"), 2);
+        assert_eq!(set.get("blue"), 3);
+        assert_eq!(set.get("pink"), 0);
+        assert_eq!(cubes("0 red, 4 blue"), cubes("4 blue"));
+        assert_eq!(cubes("5 purple, 1 teal").get("purple"), 5);
+        assert!(CubeSet::parse("1 red, 2 red").is_err());
+        assert!(CubeSet::parse("1 red, 0 red").is_err());
+        assert!(CubeSet::parse("1 re-d").is_err());
+        assert!(CubeSet::parse("red").is_err());
+        assert!(CubeSet::parse("").is_err());
+        assert!(CubeSet::parse("-1 red").is_err());
+    }
+
+    #[test]
+    fn test_contains_and_max() {
+        let bag = cubes("12 red, 13 green, 14 blue");
+        assert!(bag.contains(&cubes("12 red, 1 blue")));
+        assert!(!bag.contains(&cubes("13 red")));
+        assert!(!bag.contains(&cubes("1 purple")));
+        assert!(cubes("1 purple").is_possible(&cubes("1 purple, 1 red")));
+        let mut set = cubes("3 red, 1 teal");
+        set.max(&cubes("1 red, 2 blue"));
+        assert_eq!(set, cubes("3 red, 2 blue, 1 teal"));
+    }
+
+    #[test]
+    fn test_display_and_power() {
+        let set = cubes("4 red, 2 green, 6 blue");
+        assert_eq!(set.to_string(), "6 blue, 2 green, 4 red");
+        assert_eq!(set.to_string().parse::<CubeSet>().unwrap(), set);
+        assert_eq!(set.power(), 48);
+        assert_eq!(set.total(), 12);
+        assert_eq!(CubeSet::default().power(), 0);
+    }
+
+    #[test]
+    fn test_parse_bag() {
+        let bag = parse_bag("# the puzzle bag\n12 red\n13 green, 14 blue\n\n").unwrap();
+        assert_eq!(bag, cubes("12 red, 13 green, 14 blue"));
+        assert!(parse_bag("# nothing\n").is_err());
+        assert!(parse_bag("12 red\n1 red").is_err());
+    }
+
+    #[test]
+    fn test_parse_game() {
+        let game = GameRecord::parse("Game 0: 0 red, 2 green; 3 blue").unwrap();
+        assert_eq!(game.id, 0);
+        assert_eq!(game.draws.len(), 2);
+        assert_eq!(game.draws[0], cubes("2 green"));
+        assert_eq!(game.draws[1].get("blue"), 3);
+        assert!(GameRecord::parse("Game 1 1 red").is_err());
+        assert!(GameRecord::parse("Gamer 1: 1 red").is_err());
+        assert!(GameRecord::parse("Game1: 1 red").is_err());
+        assert!(GameRecord::parse("Game x: 1 red").is_err());
+        assert!(GameRecord::parse("Game 1: 1 red;").is_err());
+        assert!(GameRecord::parse("Game 1: 1 red, 1 red").is_err());
+    }
+
+    #[test]
+    fn test_is_possible() {
+        let game = GameRecord::parse("Game 1: 1 red, 2 green; 3 blue").unwrap();
+        assert!(game.is_possible(&cubes("1 red, 2 green, 3 blue")));
+        assert!(!game.is_possible(&cubes("1 red, 2 green, 2 blue")));
+    }
+
+    #[test]
+    fn test_minimum_bag() {
+        let game =
+            GameRecord::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        assert_eq!(game.minimum_bag(), cubes("4 red, 2 green, 6 blue"));
+    }
+
+    #[test]
+    fn test_summarize_json() {
+        let games = parse_games(&[
+            String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            String::from("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green"),
+        ])
+        .unwrap();
+        let summary = summarize(games, &cubes("12 red, 13 green, 14 blue"));
+        assert_eq!(summary.possible_sum, 1);
+        assert_eq!(summary.power_sum, 48 + 1560);
+        assert!(!summary.games[1].possible);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(
+            json["games"][0],
+            serde_json::json!({
+                "id": 1,
+                "draws": [
+                    {"blue": 3, "red": 4},
+                    {"blue": 6, "green": 2, "red": 1},
+                    {"green": 2}
+                ],
+                "minimum_bag": {"blue": 6, "green": 2, "red": 4},
+                "power": 48,
+                "possible": true
+            })
+        );
+        assert_eq!(json["bag"]["green"], 13);
+    }
+
+    #[test]
+    fn test_parse_games_line_numbers() {
+        let lines = vec![
+            String::from("Game 1: 1 red"),
+            String::from(""),
+            String::from("Game 2: 1 p1nk"),
+        ];
+        let error = parse_games(&lines).unwrap_err().to_string();
+        assert!(error.starts_with("line 3: game 2:"), "{}", error);
+    }
+}
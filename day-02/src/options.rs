@@ -0,0 +1,63 @@
+use anyhow::Error;
+use clap::Args;
+
+use crate::{parse_bag, CubeSet};
+
+// The bag to check games against, shared by both day 2 binaries
+#[derive(Args, Debug, Clone)]
+pub struct BagArgs {
+    #[arg(
+        long = "bag",
+        help = "Cubes in the bag, as in a draw",
+        default_value = "12 red, 13 green, 14 blue",
+        conflicts_with = "bag_file"
+    )]
+    pub bag: CubeSet,
+    #[arg(
+        long = "bag-file",
+        help = "Read the bag from a file, one '<count> <color>' per line"
+    )]
+    pub bag_file: Option<String>,
+}
+
+impl BagArgs {
+    pub fn bag(&self) -> Result<CubeSet, Error> {
+        match &self.bag_file {
+            Some(file) => parse_bag(
+                &std::fs::read_to_string(file).map_err(|e| anyhow::anyhow!("{}: {}", file, e))?,
+            ),
+            None => Ok(self.bag.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct Wrapper {
+        #[command(flatten)]
+        bag: BagArgs,
+    }
+
+    #[test]
+    fn test_bag() {
+        let args = Wrapper::try_parse_from(["test"]).unwrap();
+        assert_eq!(
+            args.bag.bag().unwrap(),
+            CubeSet::parse("12 red, 13 green, 14 blue").unwrap()
+        );
+        let file = std::env::temp_dir().join(format!("day-02-bag-{}", std::process::id()));
+        std::fs::write(&file, "# bag\n1 red\n2 teal\n").unwrap();
+        let path = file.to_str().unwrap();
+        let args = Wrapper::try_parse_from(["test", "--bag-file", path]).unwrap();
+        assert_eq!(
+            args.bag.bag().unwrap(),
+            CubeSet::parse("1 red, 2 teal").unwrap()
+        );
+        assert!(Wrapper::try_parse_from(["test", "--bag-file", path, "--bag", "1 red"]).is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+}
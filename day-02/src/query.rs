@@ -0,0 +1,242 @@
+use std::collections::BTreeSet;
+
+use crate::{CubeSet, GameRecord};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub game: u32,
+    // 1-based index of the draw within the game
+    pub draw: usize,
+    pub color: String,
+    pub count: u32,
+    pub available: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetPlan {
+    pub bag: CubeSet,
+    pub games: Vec<u32>,
+}
+
+struct Search<'a> {
+    colors: Vec<&'a str>,
+    // distinct non-zero counts each color takes in a game's minimum bag
+    candidates: Vec<Vec<u32>>,
+    bags: &'a [(u32, CubeSet)],
+    best: Option<BudgetPlan>,
+}
+
+impl Search<'_> {
+    // chooses a count for colors[depth..] with at most `budget` cubes left,
+    // `games` are the indices of the games that still fit the counts chosen
+    fn run(&mut self, depth: usize, budget: u64, bag: &mut CubeSet, games: Vec<usize>) {
+        if let Some(best) = &self.best {
+            if games.len() <= best.games.len() {
+                return;
+            }
+        }
+        if depth == self.colors.len() {
+            self.best = Some(BudgetPlan {
+                bag: bag.clone(),
+                games: games.iter().map(|index| self.bags[*index].0).collect(),
+            });
+            return;
+        }
+        let color = self.colors[depth];
+        // the larger counts first, they keep the most games
+        let candidates = std::iter::once(0)
+            .chain(self.candidates[depth].iter().copied())
+            .filter(|count| u64::from(*count) <= budget)
+            .collect::<Vec<u32>>();
+        for count in candidates.into_iter().rev() {
+            let fits = games
+                .iter()
+                .copied()
+                .filter(|index| self.bags[*index].1.get(color) <= count)
+                .collect();
+            bag.insert(color, count);
+            self.run(depth + 1, budget - u64::from(count), bag, fits);
+            bag.insert(color, 0);
+        }
+    }
+}
+
+//
+// functions
+//
+
+// the smallest bag that makes every game possible
+pub fn minimum_bag(games: &[GameRecord]) -> CubeSet {
+    let mut bag = CubeSet::default();
+    for game in games.iter() {
+        bag.max(&game.minimum_bag());
+    }
+    bag
+}
+
+// the first draw that does not fit in the bag, colors within a draw are
+// checked in name order
+pub fn first_violation(game: &GameRecord, bag: &CubeSet) -> Option<Violation> {
+    game.draws.iter().enumerate().find_map(|(n, draw)| {
+        draw.iter()
+            .find(|(color, count)| *count > bag.get(color))
+            .map(|(color, count)| Violation {
+                game: game.id,
+                draw: n + 1,
+                color: color.to_string(),
+                count,
+                available: bag.get(color),
+            })
+    })
+}
+
+pub fn violations(games: &[GameRecord], bag: &CubeSet) -> Vec<Violation> {
+    games
+        .iter()
+        .filter_map(|game| first_violation(game, bag))
+        .collect()
+}
+
+// The bag holding at most `budget` cubes that makes the most games possible.
+// Only counts some game needs are worth trying, so the search is exact while
+// branching over those per color and pruning branches that cannot beat the
+// best found so far.
+pub fn best_bag_for_budget(games: &[GameRecord], budget: u64) -> BudgetPlan {
+    let bags = games
+        .iter()
+        .map(|game| (game.id, game.minimum_bag()))
+        .collect::<Vec<(u32, CubeSet)>>();
+    let colors = bags
+        .iter()
+        .flat_map(|(_, bag)| bag.iter().map(|(color, _)| color))
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .collect::<Vec<&str>>();
+    let candidates = colors
+        .iter()
+        .map(|color| {
+            bags.iter()
+                .map(|(_, bag)| bag.get(color))
+                .filter(|count| *count > 0)
+                .collect::<BTreeSet<u32>>()
+                .into_iter()
+                .collect()
+        })
+        .collect();
+    let mut search = Search {
+        colors,
+        candidates,
+        bags: &bags,
+        best: None,
+    };
+    search.run(
+        0,
+        budget,
+        &mut CubeSet::default(),
+        (0..bags.len()).collect(),
+    );
+    search.best.unwrap_or(BudgetPlan {
+        bag: CubeSet::default(),
+        games: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    fn example() -> Vec<GameRecord> {
+        let lines = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .map(String::from);
+        parse_games(&lines).unwrap()
+    }
+
+    fn cubes(input: &str) -> CubeSet {
+        CubeSet::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_minimum_bag() {
+        assert_eq!(minimum_bag(&example()), cubes("20 red, 13 green, 15 blue"));
+        assert_eq!(minimum_bag(&[]), CubeSet::default());
+    }
+
+    #[test]
+    fn test_violations() {
+        let bag = cubes("12 red, 13 green, 14 blue");
+        let found = violations(&example(), &bag);
+        assert_eq!(
+            found,
+            vec![
+                Violation {
+                    game: 3,
+                    draw: 1,
+                    color: "red".to_string(),
+                    count: 20,
+                    available: 12
+                },
+                Violation {
+                    game: 4,
+                    draw: 3,
+                    color: "blue".to_string(),
+                    count: 15,
+                    available: 14
+                },
+            ]
+        );
+        assert_eq!(first_violation(&example()[0], &bag), None);
+    }
+
+    #[test]
+    fn test_best_bag_for_budget() {
+        let games = example();
+        // every game fits the overall minimum bag of 48 cubes
+        let plan = best_bag_for_budget(&games, 48);
+        assert_eq!(plan.games, vec![1, 2, 3, 4, 5]);
+        assert_eq!(plan.bag, cubes("20 red, 13 green, 15 blue"));
+        // game 1, 2 and 5 need 4+2+6, 1+3+4 and 6+3+2 cubes
+        let plan = best_bag_for_budget(&games, 13);
+        assert_eq!(plan.games.len(), 2);
+        assert!(plan.bag.total() <= 13);
+        let plan = best_bag_for_budget(&games, 15);
+        assert_eq!(plan.games, vec![1, 2, 5]);
+        assert_eq!(best_bag_for_budget(&games, 0).games, Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_best_bag_matches_brute_force() {
+        let games = example();
+        for budget in (0..50).step_by(3) {
+            let plan = best_bag_for_budget(&games, budget);
+            let mut best = 0;
+            for red in 0..=20 {
+                for green in 0..=13 {
+                    for blue in 0..=15 {
+                        if u64::from(red + green + blue) > budget {
+                            continue;
+                        }
+                        let mut bag = CubeSet::default();
+                        bag.insert("red", red);
+                        bag.insert("green", green);
+                        bag.insert("blue", blue);
+                        let count = games.iter().filter(|game| game.is_possible(&bag)).count();
+                        best = best.max(count);
+                    }
+                }
+            }
+            assert_eq!(plan.games.len(), best, "budget {}", budget);
+            assert!(plan.bag.total() <= budget);
+            for id in plan.games.iter() {
+                let game = games.iter().find(|game| game.id == *id).unwrap();
+                assert!(game.is_possible(&plan.bag));
+            }
+        }
+    }
+}
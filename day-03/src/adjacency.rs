@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Error;
+use grid::Grid;
+
+use crate::symbols::SymbolPolicy;
+use crate::{adjacent_parts, parse_part_number, part_index, PartNumber};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolTotal {
+    // symbols of this kind in the schematic
+    pub count: usize,
+    // part numbers next to them, a part touching two of them counts twice
+    pub parts: usize,
+    pub sum: u64,
+}
+
+// Bipartite graph between part numbers and the symbols they touch. Parts and
+// symbols are referred to by their index, edges are kept in both directions.
+pub struct Adjacency {
+    pub parts: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    pub part_symbols: Vec<Vec<usize>>,
+    pub symbol_parts: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    pub fn build(schematic: &Grid<char>, policy: &SymbolPolicy) -> Result<Self, Error> {
+        let parts = parse_part_number(schematic)?;
+        let index = part_index(schematic, &parts);
+        let symbols = schematic
+            .iter()
+            .filter(|(_, c)| policy.is_symbol(**c))
+            .map(|((row, column), c)| Symbol {
+                symbol: *c,
+                row,
+                column,
+            })
+            .collect::<Vec<Symbol>>();
+        let mut part_symbols = vec![vec![]; parts.len()];
+        let mut symbol_parts = vec![vec![]; symbols.len()];
+        for (s, symbol) in symbols.iter().enumerate() {
+            for p in adjacent_parts(&index, symbol.row, symbol.column) {
+                symbol_parts[s].push(p);
+                part_symbols[p].push(s);
+            }
+        }
+        Ok(Adjacency {
+            parts,
+            symbols,
+            part_symbols,
+            symbol_parts,
+        })
+    }
+
+    pub fn is_counted(&self, part: usize) -> bool {
+        !self.part_symbols[part].is_empty()
+    }
+
+    // parts next to more than one symbol
+    pub fn shared_parts(&self) -> Vec<usize> {
+        (0..self.parts.len())
+            .filter(|part| self.part_symbols[*part].len() > 1)
+            .collect()
+    }
+
+    pub fn isolated_symbols(&self) -> Vec<usize> {
+        (0..self.symbols.len())
+            .filter(|symbol| self.symbol_parts[*symbol].is_empty())
+            .collect()
+    }
+
+    pub fn totals(&self) -> BTreeMap<char, SymbolTotal> {
+        let mut totals: BTreeMap<char, SymbolTotal> = BTreeMap::new();
+        for (symbol, parts) in self.symbols.iter().zip(self.symbol_parts.iter()) {
+            let total = totals.entry(symbol.symbol).or_default();
+            total.count += 1;
+            total.parts += parts.len();
+            total.sum += parts
+                .iter()
+                .map(|part| u64::from(self.parts[*part].number))
+                .sum::<u64>();
+        }
+        totals
+    }
+
+    // the report numbers rows and columns from 1
+    pub fn report(&self) -> String {
+        let part = |p: usize| {
+            let part = &self.parts[p];
+            format!("{} at {}:{}", part.number, part.row + 1, part.column + 1)
+        };
+        let symbol = |s: usize| {
+            let symbol = &self.symbols[s];
+            format!(
+                "{} at {}:{}",
+                symbol.symbol,
+                symbol.row + 1,
+                symbol.column + 1
+            )
+        };
+        let mut out = String::new();
+        let counted = (0..self.parts.len())
+            .filter(|p| self.is_counted(*p))
+            .count();
+        writeln!(
+            out,
+            "{} parts, {} next to a symbol, {} symbols",
+            self.parts.len(),
+            counted,
+            self.symbols.len()
+        )
+        .unwrap();
+        writeln!(out, "\nparts and the symbols they touch:").unwrap();
+        for p in 0..self.parts.len() {
+            let symbols = self.part_symbols[p]
+                .iter()
+                .map(|s| symbol(*s))
+                .collect::<Vec<String>>();
+            let symbols = if symbols.is_empty() {
+                "-".to_string()
+            } else {
+                symbols.join(", ")
+            };
+            writeln!(out, "  {}: {}", part(p), symbols).unwrap();
+        }
+        writeln!(out, "\nparts touching several symbols:").unwrap();
+        for p in self.shared_parts() {
+            writeln!(out, "  {}", part(p)).unwrap();
+        }
+        writeln!(out, "\nsymbols with no parts:").unwrap();
+        for s in self.isolated_symbols() {
+            writeln!(out, "  {}", symbol(s)).unwrap();
+        }
+        writeln!(out, "\ntotals by symbol:").unwrap();
+        for (c, total) in self.totals() {
+            writeln!(
+                out,
+                "  {} {} symbols, {} parts, sum {}",
+                c, total.count, total.parts, total.sum
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Adjacency {
+        let exa = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+        Adjacency::build(&Grid::from_lines(&exa).unwrap(), &SymbolPolicy::default()).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let adjacency = example();
+        assert_eq!(adjacency.parts.len(), 10);
+        assert_eq!(adjacency.symbols.len(), 6);
+        // 114 and 58 touch nothing
+        assert!(!adjacency.is_counted(1));
+        assert!(!adjacency.is_counted(5));
+        let counted = (0..adjacency.parts.len())
+            .filter(|p| adjacency.is_counted(*p))
+            .map(|p| adjacency.parts[p].number)
+            .sum::<u32>();
+        assert_eq!(counted, 4361);
+        assert_eq!(adjacency.symbol_parts[0], vec![0, 2]);
+        assert_eq!(adjacency.part_symbols[2], vec![0]);
+    }
+
+    #[test]
+    fn test_shared_and_isolated() {
+        let grid = Grid::from_lines(&["*..#", ".12.", "...."]).unwrap();
+        let adjacency = Adjacency::build(&grid, &SymbolPolicy::default()).unwrap();
+        assert_eq!(adjacency.shared_parts(), vec![0]);
+        assert_eq!(adjacency.isolated_symbols(), Vec::<usize>::new());
+        let grid = Grid::from_lines(&["*...", "..#.", "1..."]).unwrap();
+        let adjacency = Adjacency::build(&grid, &SymbolPolicy::default()).unwrap();
+        assert_eq!(adjacency.isolated_symbols(), vec![0, 1]);
+        assert!(adjacency.shared_parts().is_empty());
+    }
+
+    #[test]
+    fn test_totals() {
+        let totals = example().totals();
+        assert_eq!(
+            totals[&'*'],
+            SymbolTotal {
+                count: 3,
+                parts: 5,
+                sum: 467 + 35 + 617 + 755 + 598
+            }
+        );
+        assert_eq!(totals[&'#'].sum, 633);
+        assert_eq!(totals[&'$'].parts, 1);
+        assert_eq!(totals.len(), 4);
+    }
+
+    #[test]
+    fn test_report() {
+        let grid = Grid::from_lines(&["12.", "..*", "#.."]).unwrap();
+        let report = Adjacency::build(&grid, &SymbolPolicy::default())
+            .unwrap()
+            .report();
+        let expect = [
+            "1 parts, 1 next to a symbol, 2 symbols",
+            "",
+            "parts and the symbols they touch:",
+            "  12 at 1:1: * at 2:3",
+            "",
+            "parts touching several symbols:",
+            "",
+            "symbols with no parts:",
+            "  # at 3:1",
+            "",
+            "totals by symbol:",
+            "  # 1 symbols, 0 parts, sum 0",
+            "  * 1 symbols, 1 parts, sum 12",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(report, expect);
+    }
+}
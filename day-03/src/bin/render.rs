@@ -0,0 +1,64 @@
+use anyhow::Error;
+use clap::Parser;
+use day_03::options::SchematicArgs;
+use day_03::read_schematic;
+use day_03::render::{classify, render, Cell, Window};
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 3 schematic renderer"
+)]
+struct Args {
+    #[arg(
+        short = 'f',
+        long = "input",
+        help = "Input file to use, stdin if not present",
+        default_value = "-"
+    )]
+    file: String,
+    #[command(flatten)]
+    schematic: SchematicArgs,
+    #[arg(
+        long = "row",
+        help = "Only show the rows and columns around this 1-based row and column",
+        requires = "column",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    row: Option<u64>,
+    #[arg(long = "column", requires = "row", value_parser = clap::value_parser!(u64).range(1..))]
+    column: Option<u64>,
+    #[arg(
+        long = "radius",
+        help = "Cells to show on each side",
+        default_value_t = 5
+    )]
+    radius: usize,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let policy = args.schematic.policy();
+    let schematic = read_schematic(&args.file, args.schematic.ragged, &policy)?;
+    policy.check(&schematic)?;
+    let cells = classify(&schematic, &policy)?;
+    let window = match (args.row, args.column) {
+        (Some(row), Some(column)) => Some(Window {
+            row: row as usize - 1,
+            column: column as usize - 1,
+            radius: args.radius,
+        }),
+        _ => None,
+    };
+    print!("{}", render(&schematic, &cells, window.as_ref())?);
+    println!(
+        "{}counted {}uncounted {}symbol {}gear\x1b[0m",
+        Cell::Counted.color(),
+        Cell::Uncounted.color(),
+        Cell::Symbol.color(),
+        Cell::Gear.color()
+    );
+    Ok(())
+}
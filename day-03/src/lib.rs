@@ -0,0 +1,688 @@
+use anyhow::Error;
+use grid::Grid;
+use std::io::Read;
+
+pub mod adjacency;
+pub mod options;
+pub mod render;
+pub mod symbols;
+
+use symbols::SymbolPolicy;
+
+#[derive(Debug, PartialEq)]
+pub struct PartNumber {
+    pub number: u32,
+    pub width: usize,
+    pub row: usize,
+    pub column: usize,
+}
+
+// what to do with rows shorter or longer than the others
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Ragged {
+    Reject,
+    // fill short rows with the blank character
+    Pad,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Gear {
+    pub row: usize,
+    pub column: usize,
+    pub parts: [u32; 2],
+}
+
+impl Gear {
+    pub fn ratio(&self) -> u64 {
+        u64::from(self.parts[0]) * u64::from(self.parts[1])
+    }
+}
+
+//
+// functions
+//
+
+// One line per row. Trailing whitespace and trailing blank lines are dropped,
+// so an editor stripping them does not change the schematic, and empty input
+// is an empty schematic. Trimmed rows are padded back with the blank, a row is
+// only ragged when it is shorter than the widest row before trimming. When
+// whitespace is a symbol only a stray '\r' is dropped, the rest is part of the
+// row.
+pub fn parse_schematic(
+    input: &str,
+    ragged: Ragged,
+    policy: &SymbolPolicy,
+) -> Result<Grid<char>, Error> {
+    let mut lines = input
+        .lines()
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if policy.whitespace_is_symbol {
+                (line, line)
+            } else {
+                (line, line.trim_end())
+            }
+        })
+        .collect::<Vec<(&str, &str)>>();
+    while lines.last().is_some_and(|(_, line)| line.is_empty()) {
+        lines.pop();
+    }
+    if ragged == Ragged::Reject {
+        let width = lines
+            .iter()
+            .map(|(_, line)| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (n, (line, _)) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found < width {
+                return Err(anyhow::anyhow!(
+                    "ragged schematic, line {}: expected {} characters, found {}",
+                    n + 1,
+                    width,
+                    found
+                ));
+            }
+        }
+    }
+    let rows = lines.iter().map(|(_, line)| *line).collect::<Vec<&str>>();
+    Ok(Grid::from_lines_padded(&rows, policy.blank))
+}
+
+pub fn read_schematic(
+    file: &str,
+    ragged: Ragged,
+    policy: &SymbolPolicy,
+) -> Result<Grid<char>, Error> {
+    let mut buf = String::new();
+    if file == "-" {
+        std::io::stdin().read_to_string(&mut buf)?;
+    } else {
+        buf = std::fs::read_to_string(file)?;
+    }
+    parse_schematic(&buf, ragged, policy)
+}
+
+pub fn parse_part_number(schematic: &Grid<char>) -> Result<Vec<PartNumber>, Error> {
+    let mut result = Vec::new();
+    for (row, line) in schematic.rows().enumerate() {
+        let mut column = 0;
+        while column < line.len() {
+            if !line[column].is_ascii_digit() {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < line.len() && line[column].is_ascii_digit() {
+                column += 1;
+            }
+            let number = line[start..column].iter().collect::<String>();
+            result.push(PartNumber {
+                number: number.parse::<u32>()?,
+                width: column - start,
+                row,
+                column: start,
+            });
+        }
+    }
+    Ok(result)
+}
+
+pub fn is_next_to_symbol(
+    schematic: &Grid<char>,
+    policy: &SymbolPolicy,
+    part_number: &PartNumber,
+) -> bool {
+    (part_number.column..part_number.column + part_number.width).any(|column| {
+        schematic
+            .neighbours8(part_number.row, column)
+            .filter_map(|(row, column)| schematic.get(row, column))
+            .any(|c| policy.is_symbol(*c))
+    })
+}
+
+pub fn sum_partnumber(schematic: &Grid<char>, policy: &SymbolPolicy) -> Result<u32, Error> {
+    let sum = parse_part_number(schematic)?
+        .iter()
+        .map(|n| {
+            tracing::debug!("part number: {:?}", n);
+            if is_next_to_symbol(schematic, policy, n) {
+                tracing::debug!("part number: {}", n.number);
+                n.number
+            } else {
+                0
+            }
+        })
+        .sum::<u32>();
+    Ok(sum)
+}
+
+// every cell holds the index of the part number covering it
+pub fn part_index(schematic: &Grid<char>, parts: &[PartNumber]) -> Grid<Option<usize>> {
+    let mut index = schematic.map(|_| None);
+    for (n, part) in parts.iter().enumerate() {
+        for column in part.column..part.column + part.width {
+            if let Some(cell) = index.get_mut(part.row, column) {
+                *cell = Some(n);
+            }
+        }
+    }
+    index
+}
+
+// distinct part numbers touching a cell, in index order
+pub fn adjacent_parts(index: &Grid<Option<usize>>, row: usize, column: usize) -> Vec<usize> {
+    let mut found = index
+        .neighbours8(row, column)
+        .filter_map(|(row, column)| *index.get(row, column)?)
+        .collect::<Vec<usize>>();
+    found.sort();
+    found.dedup();
+    found
+}
+
+// a gear is a '*' touching exactly two part numbers, if '*' is a symbol at all
+pub fn find_gears(schematic: &Grid<char>, policy: &SymbolPolicy) -> Result<Vec<Gear>, Error> {
+    let parts = parse_part_number(schematic)?;
+    let index = part_index(schematic, &parts);
+    let gears = schematic
+        .iter()
+        .filter(|(_, c)| **c == '*' && policy.is_symbol(**c))
+        .filter_map(
+            |((row, column), _)| match adjacent_parts(&index, row, column)[..] {
+                [first, second] => Some(Gear {
+                    row,
+                    column,
+                    parts: [parts[first].number, parts[second].number],
+                }),
+                _ => None,
+            },
+        )
+        .collect();
+    Ok(gears)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(lines: &[String]) -> Grid<char> {
+        Grid::from_lines(lines).unwrap()
+    }
+
+    #[test]
+    fn test_is_next_to_symbol() {
+        let s1 = vec![
+            "467..114..".to_string(),
+            "...*......".to_string(),
+            "..35..633.".to_string(),
+        ];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 0,
+            column: 0,
+        };
+        let p2 = PartNumber {
+            number: 114,
+            width: 3,
+            row: 0,
+            column: 5,
+        };
+        let p3 = PartNumber {
+            number: 35,
+            width: 2,
+            row: 2,
+            column: 2,
+        };
+        let p4 = PartNumber {
+            number: 633,
+            width: 3,
+            row: 2,
+            column: 6,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+        assert!(!is_next_to_symbol(
+            &grid(&s1),
+            &SymbolPolicy::default(),
+            &p2
+        ));
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p3));
+        assert!(!is_next_to_symbol(
+            &grid(&s1),
+            &SymbolPolicy::default(),
+            &p4
+        ));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_upleft() {
+        let s1 = vec!["*.........".to_string(), ".467.114..".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 1,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_upright() {
+        let s1 = vec!["....*.....".to_string(), ".467.114..".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 1,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_left() {
+        let s1 = vec!["..........".to_string(), "*467.114..".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 1,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_right() {
+        let s1 = vec!["..........".to_string(), ".467*114..".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 1,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_loleft() {
+        let s1 = vec![".467.114..".to_string(), "*.........".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 0,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_loright() {
+        let s1 = vec![".467.114..".to_string(), "....*.....".to_string()];
+        let p1 = PartNumber {
+            number: 467,
+            width: 3,
+            row: 0,
+            column: 1,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &p1));
+    }
+
+    #[test]
+    fn test_is_next_bottom_right_corner() {
+        let s1 = vec![
+            "467..114..".to_string(),
+            "...*......".to_string(),
+            "..35..633.".to_string(),
+            ".........*".to_string(),
+        ];
+        let pn = PartNumber {
+            number: 633,
+            width: 3,
+            row: 2,
+            column: 6,
+        };
+        assert!(is_next_to_symbol(&grid(&s1), &SymbolPolicy::default(), &pn));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_not_included() {
+        let s1 = vec!["......29..".to_string(), ".........*".to_string()];
+        let p1 = PartNumber {
+            number: 29,
+            width: 2,
+            row: 0,
+            column: 6,
+        };
+        assert!(!is_next_to_symbol(
+            &grid(&s1),
+            &SymbolPolicy::default(),
+            &p1
+        ));
+    }
+
+    #[test]
+    fn test_is_next_to_symbol_not_included2() {
+        let s1 = vec![
+            String::from("..........................*..889*....89............675..........%.......29..427...................508..&........&...641..................455"),
+            String::from("..........897...960......403.....971...*......806.....@.363................*......9+..............*.....464...................586....282*..."),
+        ];
+        let p1 = PartNumber {
+            number: 29,
+            width: 2,
+            row: 0,
+            column: 72,
+        };
+        assert!(!is_next_to_symbol(
+            &grid(&s1),
+            &SymbolPolicy::default(),
+            &p1
+        ));
+    }
+
+    #[test]
+    fn test_parse_partnumber() {
+        let s1 = vec![
+            "467..114..".to_string(),
+            "...*......".to_string(),
+            "..35..633.".to_string(),
+        ];
+        let expect = vec![
+            PartNumber {
+                number: 467,
+                width: 3,
+                row: 0,
+                column: 0,
+            },
+            PartNumber {
+                number: 114,
+                width: 3,
+                row: 0,
+                column: 5,
+            },
+            PartNumber {
+                number: 35,
+                width: 2,
+                row: 2,
+                column: 2,
+            },
+            PartNumber {
+                number: 633,
+                width: 3,
+                row: 2,
+                column: 6,
+            },
+        ];
+        assert_eq!(parse_part_number(&grid(&s1)).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_example() {
+        let exa = vec![
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 4361;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_sum_possible_last_char_num() {
+        let exa = ["..........................*..889*....89............675..........%.......29..427...................508..&........&...641..................455",
+            "..........897...960......403.....971...*......806.....@.363................*......9+..............*.....464...................586....282*..."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 5172;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_sum_possible_overcount() {
+        let exa = [".....984...+......&..618.39.493.289..21....*....379.600...........16.642..162....256........................................*....403........",
+            "...............168........*........*...*....326...............*...............*...+..............413.*.....+293.769*620....674..............",
+            "647.................949..........502...748..............692...208.......271..903..................=..132.........................506$..832.."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 6867;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_addl_ex1() {
+        let exa = vec![
+            "12.......*..",
+            "+.........34",
+            ".......-12..",
+            "..78........",
+            "..*....60...",
+            "78..........",
+            ".......23...",
+            "....90*12...",
+            "............",
+            "2.2......12.",
+            ".*.........*",
+            "1.1.......56",
+        ];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 413;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_addl_ex2() {
+        let exa = vec![
+            "12.......*..",
+            "+.........34",
+            ".......-12..",
+            "..78........",
+            "..*....60...",
+            "78.........9",
+            ".5.....23..$",
+            "8...90*12...",
+            "............",
+            "2.2......12.",
+            ".*.........*",
+            "1.1..503+.56",
+        ];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 925;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_add2() {
+        let exa = [".*1", "1.."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect = 2;
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            expect
+        );
+    }
+
+    #[test]
+    fn test_find_gears() {
+        let exa = ["467..114..", "...*......", "..35..633."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let gears = find_gears(&grid(&exa), &SymbolPolicy::default()).unwrap();
+        assert_eq!(
+            gears,
+            vec![Gear {
+                row: 1,
+                column: 3,
+                parts: [467, 35]
+            }]
+        );
+        assert_eq!(gears[0].ratio(), 16345);
+    }
+
+    #[test]
+    fn test_find_gears_right_and_same_number() {
+        // numbers starting right of the '*' count, a number touching it through
+        // two cells counts once and equal numbers in different places count twice
+        let exa = ["..*123", ".45...", "..*...", "..77.."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let gears = find_gears(&grid(&exa), &SymbolPolicy::default()).unwrap();
+        assert_eq!(gears.len(), 2);
+        assert_eq!(gears[0].parts, [123, 45]);
+        assert_eq!(gears[1].parts, [45, 77]);
+        let exa = ["12*..", "..*12", ".3*4."];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let gears = find_gears(&grid(&exa), &SymbolPolicy::default()).unwrap();
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].row, 0);
+        assert_eq!(gears[0].parts, [12, 12]);
+    }
+
+    #[test]
+    fn test_symbol_policy() {
+        let exa = ["1*2", "3#.", "..4"];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let hash_only = SymbolPolicy {
+            symbols: Some(['#'].into_iter().collect()),
+            ..SymbolPolicy::default()
+        };
+        assert_eq!(
+            sum_partnumber(&grid(&exa), &SymbolPolicy::default()).unwrap(),
+            10
+        );
+        assert_eq!(sum_partnumber(&grid(&exa), &hash_only).unwrap(), 10);
+        assert_eq!(
+            find_gears(&grid(&exa), &SymbolPolicy::default())
+                .unwrap()
+                .len(),
+            0
+        );
+        let exa = ["1*2", "...", " 4 "];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            find_gears(&grid(&exa), &SymbolPolicy::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(find_gears(&grid(&exa), &hash_only).unwrap().len(), 0);
+        let spaces = SymbolPolicy {
+            whitespace_is_symbol: true,
+            ..SymbolPolicy::default()
+        };
+        assert_eq!(sum_partnumber(&grid(&exa), &hash_only).unwrap(), 0);
+        assert_eq!(sum_partnumber(&grid(&exa), &spaces).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_parse_schematic_trailing() {
+        let schematic = parse_schematic(
+            "1*.  \r\n..2\n\n\n",
+            Ragged::Reject,
+            &SymbolPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(schematic, grid(&["1*.".to_string(), "..2".to_string()]));
+        // leading whitespace is kept so columns do not move
+        let schematic =
+            parse_schematic(" 1*\n..2\n", Ragged::Reject, &SymbolPolicy::default()).unwrap();
+        assert_eq!(schematic.get(0, 1), Some(&'1'));
+    }
+
+    #[test]
+    fn test_parse_schematic_trailing_symbol() {
+        let spaces = SymbolPolicy {
+            whitespace_is_symbol: true,
+            ..SymbolPolicy::default()
+        };
+        for input in [" 12\n...\n", "12 \n...\n", "12 \r\n...\r\n"] {
+            for ragged in [Ragged::Reject, Ragged::Pad] {
+                let schematic = parse_schematic(input, ragged, &spaces).unwrap();
+                assert_eq!(schematic.width(), 3);
+                assert_eq!(sum_partnumber(&schematic, &spaces).unwrap(), 12);
+            }
+        }
+        // without the flag the trailing space is trimmed and padded as blank
+        let schematic =
+            parse_schematic("12 \n...\n", Ragged::Pad, &SymbolPolicy::default()).unwrap();
+        assert_eq!(schematic.get(0, 2), Some(&'.'));
+        assert_eq!(sum_partnumber(&schematic, &spaces).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_parse_schematic_trailing_blank() {
+        // rectangular, but only some rows end in whitespace
+        let blank = SymbolPolicy {
+            blank: ' ',
+            ..SymbolPolicy::default()
+        };
+        for policy in [SymbolPolicy::default(), blank] {
+            let schematic = parse_schematic("1*3  \n.....\n", Ragged::Reject, &policy).unwrap();
+            assert_eq!(schematic.width(), 5);
+            assert_eq!(schematic.get(0, 4), Some(&policy.blank));
+            assert_eq!(sum_partnumber(&schematic, &policy).unwrap(), 4);
+        }
+    }
+
+    #[test]
+    fn test_parse_schematic_ragged() {
+        let input = "467..114..\n...*\n..35..633.\n";
+        let error = parse_schematic(input, Ragged::Reject, &SymbolPolicy::default())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "ragged schematic, line 2: expected 10 characters, found 4"
+        );
+        let schematic = parse_schematic(input, Ragged::Pad, &SymbolPolicy::default()).unwrap();
+        assert_eq!(schematic.width(), 10);
+        assert_eq!(schematic.get(1, 9), Some(&'.'));
+        assert_eq!(
+            sum_partnumber(&schematic, &SymbolPolicy::default()).unwrap(),
+            467 + 35
+        );
+        // a blank line inside the schematic is a short row
+        assert!(parse_schematic("1*\n\n.2", Ragged::Reject, &SymbolPolicy::default()).is_err());
+        let underscore = SymbolPolicy {
+            blank: '_',
+            ..SymbolPolicy::default()
+        };
+        let schematic = parse_schematic("1*\n\n.2", Ragged::Pad, &underscore).unwrap();
+        assert_eq!(schematic.row(1), Some(&['_', '_'][..]));
+    }
+
+    #[test]
+    fn test_parse_schematic_empty() {
+        for input in ["", "\n", "  \n\n"] {
+            for ragged in [Ragged::Reject, Ragged::Pad] {
+                let schematic = parse_schematic(input, ragged, &SymbolPolicy::default()).unwrap();
+                assert!(schematic.is_empty());
+                assert_eq!(
+                    sum_partnumber(&schematic, &SymbolPolicy::default()).unwrap(),
+                    0
+                );
+                assert!(find_gears(&schematic, &SymbolPolicy::default())
+                    .unwrap()
+                    .is_empty());
+                assert!(SymbolPolicy::default().check(&schematic).is_ok());
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use clap::Args;
+
+use crate::symbols::SymbolPolicy;
+use crate::Ragged;
+
+// How to read a schematic, shared by every day 3 binary
+#[derive(Args, Debug, Clone)]
+pub struct SchematicArgs {
+    #[arg(
+        long = "symbols",
+        help = "Characters that are symbols, any ASCII punctuation if not present"
+    )]
+    pub symbols: Option<String>,
+    #[arg(
+        long = "blank",
+        help = "Character for an empty cell",
+        default_value_t = '.'
+    )]
+    pub blank: char,
+    #[arg(
+        long = "whitespace-is-symbol",
+        help = "Count whitespace as a symbol instead of blank"
+    )]
+    pub whitespace_is_symbol: bool,
+    #[arg(
+        long = "ragged",
+        help = "Reject rows of different lengths or pad them with the blank character",
+        value_enum,
+        default_value_t = Ragged::Reject
+    )]
+    pub ragged: Ragged,
+}
+
+impl SchematicArgs {
+    pub fn policy(&self) -> SymbolPolicy {
+        SymbolPolicy {
+            symbols: self
+                .symbols
+                .as_ref()
+                .map(|symbols| symbols.chars().collect()),
+            blank: self.blank,
+            whitespace_is_symbol: self.whitespace_is_symbol,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::Write;
+
+use anyhow::Error;
+use grid::Grid;
+
+use crate::adjacency::Adjacency;
+use crate::find_gears;
+use crate::symbols::SymbolPolicy;
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
+    Blank,
+    // digit of a part number next to a symbol
+    Counted,
+    Uncounted,
+    Symbol,
+    Gear,
+}
+
+impl Cell {
+    pub fn color(&self) -> &'static str {
+        match self {
+            Cell::Blank => "\x1b[2m",
+            Cell::Counted => "\x1b[32m",
+            Cell::Uncounted => "\x1b[31m",
+            Cell::Symbol => "\x1b[36m",
+            Cell::Gear => "\x1b[1;33m",
+        }
+    }
+}
+
+// Rows and columns within `radius` of a cell, clipped to the schematic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    pub row: usize,
+    pub column: usize,
+    pub radius: usize,
+}
+
+//
+// functions
+//
+
+pub fn classify(schematic: &Grid<char>, policy: &SymbolPolicy) -> Result<Grid<Cell>, Error> {
+    let mut cells = schematic.map(|c| {
+        if policy.is_symbol(*c) {
+            Cell::Symbol
+        } else {
+            Cell::Blank
+        }
+    });
+    let adjacency = Adjacency::build(schematic, policy)?;
+    for (n, part) in adjacency.parts.iter().enumerate() {
+        let class = if adjacency.is_counted(n) {
+            Cell::Counted
+        } else {
+            Cell::Uncounted
+        };
+        for column in part.column..part.column + part.width {
+            if let Some(cell) = cells.get_mut(part.row, column) {
+                *cell = class;
+            }
+        }
+    }
+    for gear in find_gears(schematic, policy)? {
+        if let Some(cell) = cells.get_mut(gear.row, gear.column) {
+            *cell = Cell::Gear;
+        }
+    }
+    Ok(cells)
+}
+
+// each row prefixed with its 1-based number, colors only change when the class does
+pub fn render(
+    schematic: &Grid<char>,
+    cells: &Grid<Cell>,
+    window: Option<&Window>,
+) -> Result<String, Error> {
+    let (rows, columns) = match window {
+        Some(window) => {
+            if !schematic.contains(window.row, window.column) {
+                return Err(anyhow::anyhow!(
+                    "row {}, column {} is outside the {}x{} schematic",
+                    window.row + 1,
+                    window.column + 1,
+                    schematic.height(),
+                    schematic.width()
+                ));
+            }
+            let end = |n: usize| n.saturating_add(window.radius).saturating_add(1);
+            (
+                window.row.saturating_sub(window.radius)..end(window.row).min(schematic.height()),
+                window.column.saturating_sub(window.radius)
+                    ..end(window.column).min(schematic.width()),
+            )
+        }
+        None => (0..schematic.height(), 0..schematic.width()),
+    };
+    let number_width = schematic.height().to_string().len();
+    let mut out = String::new();
+    for row in rows {
+        write!(out, "{:>width$} ", row + 1, width = number_width).unwrap();
+        let mut current = None;
+        for column in columns.clone() {
+            let (Some(c), Some(cell)) = (schematic.get(row, column), cells.get(row, column)) else {
+                continue;
+            };
+            if current != Some(*cell) {
+                out.push_str(cell.color());
+                current = Some(*cell);
+            }
+            out.push(*c);
+        }
+        out.push_str(RESET);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schematic() -> Grid<char> {
+        Grid::from_lines(&["467..114..", "...*......", "..35..633."]).unwrap()
+    }
+
+    #[test]
+    fn test_classify() {
+        let cells = classify(&schematic(), &SymbolPolicy::default()).unwrap();
+        assert_eq!(cells.get(0, 0), Some(&Cell::Counted));
+        assert_eq!(cells.get(0, 5), Some(&Cell::Uncounted));
+        assert_eq!(cells.get(1, 3), Some(&Cell::Gear));
+        assert_eq!(cells.get(2, 6), Some(&Cell::Uncounted));
+        assert_eq!(cells.get(0, 3), Some(&Cell::Blank));
+        let grid = Grid::from_lines(&["1#."]).unwrap();
+        let cells = classify(&grid, &SymbolPolicy::default()).unwrap();
+        assert_eq!(cells.get(0, 1), Some(&Cell::Symbol));
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from_lines(&["1*", ".."]).unwrap();
+        let cells = classify(&grid, &SymbolPolicy::default()).unwrap();
+        assert_eq!(
+            render(&grid, &cells, None).unwrap(),
+            "1 \x1b[32m1\x1b[36m*\x1b[0m\n2 \x1b[2m..\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_render_window() {
+        let grid = schematic();
+        let cells = classify(&grid, &SymbolPolicy::default()).unwrap();
+        let window = Window {
+            row: 0,
+            column: 6,
+            radius: 1,
+        };
+        let out = render(&grid, &cells, Some(&window)).unwrap();
+        let lines = out.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "1 \x1b[31m114\x1b[0m");
+        assert_eq!(lines[1], "2 \x1b[2m...\x1b[0m");
+        // a huge radius shows the whole schematic
+        let window = Window {
+            radius: usize::MAX,
+            ..window
+        };
+        let out = render(&grid, &cells, Some(&window)).unwrap();
+        assert_eq!(out, render(&grid, &cells, None).unwrap());
+        let window = Window {
+            row: 3,
+            column: 0,
+            radius: 1,
+        };
+        let error = render(&grid, &cells, Some(&window))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "row 4, column 1 is outside the 3x10 schematic");
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use anyhow::Error;
+use grid::Grid;
+
+// Decides which schematic characters are symbols. Digits and the blank
+// character never are, whitespace is blank unless it is declared a symbol.
+// Without an explicit symbol set every ASCII punctuation character counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolPolicy {
+    pub symbols: Option<BTreeSet<char>>,
+    pub blank: char,
+    pub whitespace_is_symbol: bool,
+}
+
+impl Default for SymbolPolicy {
+    fn default() -> Self {
+        SymbolPolicy {
+            symbols: None,
+            blank: '.',
+            whitespace_is_symbol: false,
+        }
+    }
+}
+
+impl SymbolPolicy {
+    pub fn is_symbol(&self, c: char) -> bool {
+        if c.is_ascii_digit() || c == self.blank {
+            return false;
+        }
+        if c.is_whitespace() {
+            return self.whitespace_is_symbol;
+        }
+        match &self.symbols {
+            Some(symbols) => symbols.contains(&c),
+            None => c.is_ascii_punctuation(),
+        }
+    }
+
+    pub fn is_known(&self, c: char) -> bool {
+        c.is_ascii_digit() || c == self.blank || c.is_whitespace() || self.is_symbol(c)
+    }
+
+    // every character must be a digit, blank, whitespace or a symbol
+    pub fn check(&self, schematic: &Grid<char>) -> Result<(), Error> {
+        match schematic.iter().find(|(_, c)| !self.is_known(**c)) {
+            Some(((row, column), c)) => Err(anyhow::anyhow!(
+                "row {} column {}: '{}' is not a digit, blank or symbol",
+                row + 1,
+                column + 1,
+                c.escape_debug()
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy() {
+        let policy = SymbolPolicy::default();
+        assert!(policy.is_symbol('*'));
+        assert!(policy.is_symbol('#'));
+        assert!(!policy.is_symbol('.'));
+        assert!(!policy.is_symbol('7'));
+        assert!(!policy.is_symbol(' '));
+        assert!(!policy.is_symbol('\r'));
+        assert!(!policy.is_symbol('é'));
+        assert!(!policy.is_known('é'));
+    }
+
+    #[test]
+    fn test_explicit_policy() {
+        let policy = SymbolPolicy {
+            symbols: Some(['*', '#'].into_iter().collect()),
+            blank: '_',
+            whitespace_is_symbol: true,
+        };
+        assert!(policy.is_symbol('*'));
+        assert!(!policy.is_symbol('+'));
+        assert!(!policy.is_symbol('_'));
+        assert!(policy.is_symbol(' '));
+        assert!(policy.is_symbol('\t'));
+        assert!(!policy.is_known('.'));
+    }
+
+    #[test]
+    fn test_check() {
+        let policy = SymbolPolicy {
+            symbols: Some(['*'].into_iter().collect()),
+            ..SymbolPolicy::default()
+        };
+        let grid = Grid::from_lines(&["1.*", ". 2"]).unwrap();
+        assert!(policy.check(&grid).is_ok());
+        let grid = Grid::from_lines(&["1.*", ".#\r"]).unwrap();
+        let error = policy.check(&grid).unwrap_err().to_string();
+        assert_eq!(error, "row 2 column 2: '#' is not a digit, blank or symbol");
+        let grid = Grid::from_lines(&["1é"]).unwrap();
+        assert!(SymbolPolicy::default().check(&grid).is_err());
+    }
+}
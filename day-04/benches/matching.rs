@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_04::{parse_cards, Scratchcard};
+
+// Card file with `count` cards of 10 winning and 25 held numbers drawn from
+// 1..=max, deterministic so runs compare
+fn generate(count: u32, max: u32) -> Vec<String> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as u32 % max + 1
+    };
+    let mut draw = |n: usize| {
+        let mut numbers = Vec::with_capacity(n);
+        while numbers.len() < n {
+            let number = next();
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+        numbers
+            .iter()
+            .map(|n| format!("{:>3}", n))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    (1..=count)
+        .map(|id| format!("Card {}: {} | {}", id, draw(10), draw(25)))
+        .collect()
+}
+
+// the hash set matching the bit set replaced, to compare against
+fn matches_hashset(card: &Scratchcard) -> usize {
+    let winning = card.winning.iter().collect::<HashSet<&u32>>();
+    card.have
+        .iter()
+        .filter(|number| winning.contains(number))
+        .count()
+}
+
+fn matching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matches");
+    for (name, max) in [("small", 99), ("wide", 999)] {
+        let cards: Vec<Scratchcard> = parse_cards(&generate(10_000, max)).unwrap();
+        group.bench_with_input(BenchmarkId::new("hashset", name), &cards, |b, cards| {
+            b.iter(|| black_box(cards).iter().map(matches_hashset).sum::<usize>())
+        });
+        group.bench_with_input(BenchmarkId::new("bitset", name), &cards, |b, cards| {
+            b.iter(|| {
+                black_box(cards)
+                    .iter()
+                    .map(|card| card.matches())
+                    .sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, matching);
+criterion_main!(benches);
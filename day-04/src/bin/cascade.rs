@@ -0,0 +1,89 @@
+use anyhow::Error;
+use clap::{Parser, ValueEnum};
+use day_04::cascade::Cascade;
+use day_04::{count_cards, match_counts};
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 4 scratchcard cascade"
+)]
+struct Args {
+    #[arg(
+        long = "format",
+        help = "Print a readable report or one CSV row per card",
+        value_enum,
+        default_value_t = OutputFormat::Report
+    )]
+    format: OutputFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    Report,
+    Csv,
+}
+
+// the simulated total must agree with the part 2 count
+fn simulate(input: &[String]) -> Result<Cascade, Error> {
+    let matches = match_counts(input)?;
+    let cascade = Cascade::simulate(&matches)?;
+    let expected = count_cards(&matches)?;
+    if cascade.total() != expected {
+        return Err(anyhow::anyhow!(
+            "cascade total {} does not match card count {}",
+            cascade.total(),
+            expected
+        ));
+    }
+    Ok(cascade)
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let lines = std::io::stdin().lines();
+    let lines = lines
+        .map(|l| l.unwrap().trim().to_string())
+        .collect::<Vec<String>>();
+    let cascade = simulate(&lines)?;
+    match args.format {
+        OutputFormat::Report => print!("{}", cascade.report()),
+        OutputFormat::Csv => print!("{}", cascade.csv()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let exa = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let cascade = simulate(&exa).unwrap();
+        assert!(cascade.report().ends_with("6 cards, 30 total, depth 4\n"));
+    }
+
+    #[test]
+    fn test_wide_cascade() {
+        let numbers = (1..=79)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let cards = (1..=80)
+            .map(|id| format!("Card {}: {} | {}", id, numbers, numbers))
+            .collect::<Vec<String>>();
+        let error = simulate(&cards).unwrap_err().to_string();
+        assert_eq!(error, "card 65: card count overflows u64");
+    }
+}
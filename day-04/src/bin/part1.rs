@@ -1,44 +1,56 @@
 use anyhow::Error;
-use std::collections::HashSet;
+use clap::{Parser, ValueEnum};
+use day_04::match_counts;
+use day_04::scoring::Scoring;
 use tracing::Level;
 
-fn decode_winner(input: &[String]) -> Result<usize, Error> {
-    let mut points = 0;
-    for line in input {
-        let mut parts = line.split(':').last().unwrap().split('|');
-        let winners = parts.next().unwrap().trim().split(' ');
-        let mut winner_set = HashSet::new();
-        for winner in winners {
-            let winner = winner.trim();
-            if winner.is_empty() {
-                continue;
-            }
-            let winner = winner.parse::<usize>()?;
-            winner_set.insert(winner);
-        }
-        let cards = parts.next().unwrap().trim().split(' ');
-        let mut tally = 0;
-        for card_num in cards {
-            let card_num = card_num.trim();
-            if card_num.is_empty() {
-                continue;
-            }
-            let card_num = card_num.parse::<usize>()?;
-            if winner_set.contains(&card_num) {
-                if tally == 0 {
-                    tally = 1;
-                } else {
-                    tally *= 2;
-                }
-            }
-        }
-        points += tally;
-    }
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 4 Part 1"
+)]
+struct Args {
+    #[arg(
+        long = "scoring",
+        help = "How matches turn into points",
+        value_enum,
+        default_value_t = ScoringArg::Doubling
+    )]
+    scoring: ScoringArg,
+    #[arg(
+        long = "table",
+        help = "Comma separated points for 0, 1, 2, ... matches, used with --scoring table",
+        required_if_eq("scoring", "table")
+    )]
+    table: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScoringArg {
+    Doubling,
+    Linear,
+    Fibonacci,
+    Table,
+}
+
+fn scoring(args: &Args) -> Result<Scoring, Error> {
+    Ok(match args.scoring {
+        ScoringArg::Doubling => Scoring::Doubling,
+        ScoringArg::Linear => Scoring::Linear,
+        ScoringArg::Fibonacci => Scoring::Fibonacci,
+        ScoringArg::Table => Scoring::parse_table(args.table.as_deref().unwrap_or_default())?,
+    })
+}
 
-    Ok(points)
+fn decode_winner(input: &[String], scoring: &Scoring) -> Result<u128, Error> {
+    scoring.total(&match_counts(input)?)
 }
 
 fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let scoring = scoring(&args)?;
     tracing_subscriber::fmt()
         .with_max_level(Level::DEBUG)
         .init();
@@ -46,7 +58,7 @@ fn main() -> Result<(), Error> {
     let lines = lines
         .map(|l| l.unwrap().trim().to_string())
         .collect::<Vec<String>>();
-    let sum = decode_winner(&lines).unwrap();
+    let sum = decode_winner(&lines, &scoring)?;
     println!("{}", sum);
     Ok(())
 }
@@ -57,7 +69,7 @@ mod tests {
 
     #[test]
     fn test_example() {
-        let exa = vec![
+        let exa = [
             "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
             "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
             "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
@@ -67,7 +79,8 @@ mod tests {
         ];
         let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
         let expect_points = 13;
-        let result = decode_winner(&exa).unwrap();
+        let result = decode_winner(&exa, &Scoring::Doubling).unwrap();
         assert_eq!(result, expect_points);
+        assert_eq!(decode_winner(&exa, &Scoring::Linear).unwrap(), 9);
     }
 }
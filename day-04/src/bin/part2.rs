@@ -0,0 +1,57 @@
+use anyhow::Error;
+use day_04::{count_cards, match_counts};
+use tracing::Level;
+
+fn count_scratchcards(input: &[String]) -> Result<u64, Error> {
+    let matches = match_counts(input)?;
+    tracing::debug!("matches: {:?}", matches);
+    count_cards(&matches)
+}
+
+fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::DEBUG)
+        .init();
+    let lines = std::io::stdin().lines();
+    let lines = lines
+        .map(|l| l.unwrap().trim().to_string())
+        .collect::<Vec<String>>();
+    let sum = count_scratchcards(&lines)?;
+    println!("{}", sum);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let exa = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let exa = exa.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let expect_cards = 30;
+        let result = count_scratchcards(&exa).unwrap();
+        assert_eq!(result, expect_cards);
+    }
+
+    #[test]
+    fn test_wide_cascade() {
+        // every card matches all 79 numbers it holds and wins the rest
+        let numbers = (1..=79)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let cards = (1..=80)
+            .map(|id| format!("Card {}: {} | {}", id, numbers, numbers))
+            .collect::<Vec<String>>();
+        let error = count_scratchcards(&cards).unwrap_err().to_string();
+        assert_eq!(error, "card 65: card count overflows u64");
+    }
+}
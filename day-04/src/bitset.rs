@@ -0,0 +1,137 @@
+// Set of card numbers. Numbers below 128 are bits of a u128, which covers
+// every number on a real card, anything larger falls back to a sorted list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberSet {
+    bits: u128,
+    large: Vec<u32>,
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, number: u32) -> bool {
+        if number < u128::BITS {
+            let bit = 1u128 << number;
+            let added = self.bits & bit == 0;
+            self.bits |= bit;
+            added
+        } else {
+            match self.large.binary_search(&number) {
+                Ok(_) => false,
+                Err(at) => {
+                    self.large.insert(at, number);
+                    true
+                }
+            }
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        if number < u128::BITS {
+            self.bits & (1u128 << number) != 0
+        } else {
+            self.large.binary_search(&number).is_ok()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize + self.large.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // size of the intersection, a popcount unless both sets hold large numbers
+    pub fn common(&self, other: &NumberSet) -> usize {
+        let mut count = (self.bits & other.bits).count_ones() as usize;
+        if !self.large.is_empty() && !other.large.is_empty() {
+            let (mut a, mut b) = (self.large.iter().peekable(), other.large.iter().peekable());
+            while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+                match x.cmp(y) {
+                    std::cmp::Ordering::Less => {
+                        a.next();
+                    }
+                    std::cmp::Ordering::Greater => {
+                        b.next();
+                    }
+                    std::cmp::Ordering::Equal => {
+                        count += 1;
+                        a.next();
+                        b.next();
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    // same as `common` without building a set from `numbers` first, the
+    // small numbers are gathered into a mask for a single popcount
+    pub fn common_with(&self, numbers: &[u32]) -> usize {
+        let mut mask = 0u128;
+        let mut count = 0;
+        for &number in numbers {
+            if number < u128::BITS {
+                mask |= 1u128 << number;
+            } else if self.large.binary_search(&number).is_ok() {
+                count += 1;
+            }
+        }
+        count + (self.bits & mask).count_ones() as usize
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(numbers: I) -> Self {
+        let mut set = NumberSet::default();
+        for number in numbers {
+            if number < u128::BITS {
+                set.bits |= 1u128 << number;
+            } else {
+                set.large.push(number);
+            }
+        }
+        set.large.sort_unstable();
+        set.large.dedup();
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_contains() {
+        let mut set = NumberSet::default();
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(127));
+        assert!(set.insert(128));
+        assert!(set.insert(5000));
+        assert!(!set.insert(127));
+        assert!(!set.insert(5000));
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(0));
+        assert!(set.contains(128));
+        assert!(!set.contains(1));
+        assert!(!set.contains(129));
+    }
+
+    #[test]
+    fn test_common() {
+        let a = [41, 48, 83, 86, 17].into_iter().collect::<NumberSet>();
+        let b = [83, 86, 6, 31, 17, 9, 48, 53]
+            .into_iter()
+            .collect::<NumberSet>();
+        assert_eq!(a.common(&b), 4);
+        assert_eq!(b.common(&a), 4);
+        let a = [1, 200, 300, 999].into_iter().collect::<NumberSet>();
+        let b = [1, 2, 300, 400, 999, 1000]
+            .into_iter()
+            .collect::<NumberSet>();
+        assert_eq!(a.common(&b), 3);
+        assert_eq!(a.common(&NumberSet::default()), 0);
+        assert_eq!(a.common_with(&[1, 2, 300, 400, 999, 1000]), 3);
+        assert_eq!(a.common_with(&[]), 0);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Error;
+
+// One card after the cascade. Cards are numbered from 1 by position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardRecord {
+    pub card: usize,
+    pub matches: usize,
+    // the original plus every copy won
+    pub copies: u64,
+    // copies won directly from each earlier card
+    pub won_from: BTreeMap<usize, u64>,
+    // copies traced back to each original card, including this one
+    pub origins: BTreeMap<usize, u64>,
+    // longest chain of wins leading to a copy, 0 for an original only
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cascade {
+    pub cards: Vec<CardRecord>,
+    total: u64,
+}
+
+impl Cascade {
+    // Every card wins one copy of each of the next N cards per instance it
+    // has. Cards only win later cards, so a single forward pass settles each
+    // card before it hands out copies. Every count is checked like the
+    // part 2 count, copies double with each card that wins all the rest.
+    pub fn simulate(matches: &[usize]) -> Result<Cascade, Error> {
+        let mut cards = matches
+            .iter()
+            .enumerate()
+            .map(|(n, &matches)| CardRecord {
+                card: n + 1,
+                matches,
+                copies: 1,
+                won_from: BTreeMap::new(),
+                origins: BTreeMap::from([(n + 1, 1)]),
+                depth: 0,
+            })
+            .collect::<Vec<CardRecord>>();
+        let mut total = 0u64;
+        for n in 0..cards.len() {
+            let (settled, rest) = cards.split_at_mut(n + 1);
+            let winner = &settled[n];
+            total = total
+                .checked_add(winner.copies)
+                .ok_or_else(|| overflow(winner.card))?;
+            let end = winner.matches.min(rest.len());
+            for card in rest[..end].iter_mut() {
+                card.copies = add(card.copies, winner.copies, card.card)?;
+                let won = card.won_from.entry(winner.card).or_default();
+                *won = add(*won, winner.copies, card.card)?;
+                for (origin, copies) in winner.origins.iter() {
+                    let traced = card.origins.entry(*origin).or_default();
+                    *traced = add(*traced, *copies, card.card)?;
+                }
+                card.depth = card.depth.max(winner.depth + 1);
+            }
+        }
+        Ok(Cascade { cards, total })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn depth(&self) -> usize {
+        self.cards.iter().map(|card| card.depth).max().unwrap_or(0)
+    }
+
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        for card in self.cards.iter() {
+            writeln!(
+                out,
+                "card {}: {} matches, {} copies, depth {}",
+                card.card, card.matches, card.copies, card.depth
+            )
+            .unwrap();
+            if !card.won_from.is_empty() {
+                writeln!(out, "  won from: {}", counts(&card.won_from, ", ")).unwrap();
+            }
+            writeln!(out, "  originals: {}", counts(&card.origins, ", ")).unwrap();
+        }
+        writeln!(
+            out,
+            "{} cards, {} total, depth {}",
+            self.cards.len(),
+            self.total(),
+            self.depth()
+        )
+        .unwrap();
+        out
+    }
+
+    // one row per card, card:copies pairs separated by ';'
+    pub fn csv(&self) -> String {
+        let mut out = String::from("card,matches,copies,depth,won_from,origins\n");
+        for card in self.cards.iter() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                card.card,
+                card.matches,
+                card.copies,
+                card.depth,
+                counts(&card.won_from, ";"),
+                counts(&card.origins, ";")
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+fn overflow(card: usize) -> Error {
+    anyhow::anyhow!("card {}: card count overflows u64", card)
+}
+
+fn add(count: u64, copies: u64, card: usize) -> Result<u64, Error> {
+    count.checked_add(copies).ok_or_else(|| overflow(card))
+}
+
+fn counts(copies: &BTreeMap<usize, u64>, separator: &str) -> String {
+    copies
+        .iter()
+        .map(|(card, copies)| format!("{}:{}", card, copies))
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::count_cards;
+
+    const EXAMPLE: [usize; 6] = [4, 2, 2, 1, 0, 0];
+
+    #[test]
+    fn test_simulate() {
+        let cascade = Cascade::simulate(&EXAMPLE).unwrap();
+        let copies = cascade
+            .cards
+            .iter()
+            .map(|card| card.copies)
+            .collect::<Vec<u64>>();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total(), 30);
+        assert_eq!(cascade.depth(), 4);
+        let card4 = &cascade.cards[3];
+        assert_eq!(card4.won_from, BTreeMap::from([(1, 1), (2, 2), (3, 4)]));
+        assert_eq!(
+            card4.origins,
+            BTreeMap::from([(1, 4), (2, 2), (3, 1), (4, 1)])
+        );
+        assert_eq!(card4.depth, 3);
+        assert_eq!(cascade.cards[5].depth, 0);
+    }
+
+    #[test]
+    fn test_matches_count_cards() {
+        for matches in [&EXAMPLE[..], &[], &[5, 1], &[3, 3, 3, 3, 0, 2, 1, 0]] {
+            let cascade = Cascade::simulate(matches).unwrap();
+            assert_eq!(cascade.total(), count_cards(matches).unwrap());
+            for card in cascade.cards.iter() {
+                assert_eq!(card.origins.values().sum::<u64>(), card.copies);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let cascade = Cascade::simulate(&[64; 64]).unwrap();
+        assert_eq!(cascade.total(), u64::MAX);
+        assert_eq!(cascade.cards[63].copies, 1 << 63);
+        let error = Cascade::simulate(&[79; 80]).unwrap_err().to_string();
+        assert_eq!(error, count_cards(&[79; 80]).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = Cascade::simulate(&[1, 0]).unwrap().csv();
+        assert_eq!(
+            csv,
+            "card,matches,copies,depth,won_from,origins\n1,1,1,0,,1:1\n2,0,2,1,1:1,1:1;2:1\n"
+        );
+    }
+}
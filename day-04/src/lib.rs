@@ -0,0 +1,237 @@
+use anyhow::Error;
+
+pub mod bitset;
+pub mod cascade;
+pub mod scoring;
+
+use bitset::NumberSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Scratchcard {
+    // "Card <id>: <winning numbers> | <numbers you have>"
+    pub fn parse(line: &str) -> Result<Scratchcard, Error> {
+        let (card, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("expected 'Card <id>: <numbers>'"))?;
+        let id = card
+            .trim()
+            .strip_prefix("Card")
+            .filter(|id| id.starts_with(char::is_whitespace))
+            .ok_or_else(|| anyhow::anyhow!("expected 'Card <id>': '{}'", card))?
+            .trim();
+        let id = id
+            .parse::<u32>()
+            .map_err(|_| anyhow::anyhow!("invalid card id: '{}'", id))?;
+        let (winning, have) = numbers
+            .split_once('|')
+            .ok_or_else(|| anyhow::anyhow!("card {}: missing '|'", id))?;
+        Ok(Scratchcard {
+            id,
+            winning: parse_numbers(winning)
+                .map_err(|e| anyhow::anyhow!("card {}: winning numbers: {}", id, e))?,
+            have: parse_numbers(have)
+                .map_err(|e| anyhow::anyhow!("card {}: numbers you have: {}", id, e))?,
+        })
+    }
+
+    // how many of the numbers held are winning numbers
+    pub fn matches(&self) -> usize {
+        let winning = self.winning.iter().copied().collect::<NumberSet>();
+        winning.common_with(&self.have)
+    }
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<u32>, Error> {
+    let mut numbers = vec![];
+    for number in input.split_whitespace() {
+        let number = number
+            .parse::<u32>()
+            .map_err(|_| anyhow::anyhow!("invalid number '{}'", number))?;
+        if numbers.contains(&number) {
+            return Err(anyhow::anyhow!("duplicate number {}", number));
+        }
+        numbers.push(number);
+    }
+    Ok(numbers)
+}
+
+//
+// functions
+//
+
+// one card per line, blank lines are skipped, cards must be numbered 1, 2, 3, ...
+pub fn parse_cards(input: &[String]) -> Result<Vec<Scratchcard>, Error> {
+    let mut cards: Vec<Scratchcard> = vec![];
+    for (n, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |e: Error| anyhow::anyhow!("line {}: {}", n + 1, e);
+        let card = Scratchcard::parse(line).map_err(error)?;
+        // ids so far are exactly 1..expect, so any smaller id is a repeat
+        let expect = cards.len() as u32 + 1;
+        if card.id != 0 && card.id < expect {
+            return Err(error(anyhow::anyhow!("duplicate card {}", card.id)));
+        }
+        if card.id != expect {
+            return Err(error(anyhow::anyhow!(
+                "expected card {}, found card {}",
+                expect,
+                card.id
+            )));
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+pub fn match_counts(input: &[String]) -> Result<Vec<usize>, Error> {
+    Ok(parse_cards(input)?
+        .iter()
+        .map(|card| card.matches())
+        .collect())
+}
+
+// Every card with N matches wins one copy of each of the next N cards, copies
+// win too. Each card adds its copies to a running total over the range it
+// wins, so the pass is linear in the number of cards. Copies double with
+// every card that wins all the rest, so the count is checked.
+pub fn count_cards(matches: &[usize]) -> Result<u64, Error> {
+    // copies won by earlier cards that stop applying at each card
+    let mut expiring = vec![0u64; matches.len() + 1];
+    let mut extra = 0u64;
+    let mut total = 0u64;
+    for (n, count) in matches.iter().enumerate() {
+        let overflow = || anyhow::anyhow!("card {}: card count overflows u64", n + 1);
+        extra -= expiring[n];
+        let copies = extra.checked_add(1).ok_or_else(overflow)?;
+        total = total.checked_add(copies).ok_or_else(overflow)?;
+        let end = (n + 1 + count).min(matches.len());
+        if end > n + 1 {
+            extra = extra.checked_add(copies).ok_or_else(overflow)?;
+            expiring[end] = expiring[end].checked_add(copies).ok_or_else(overflow)?;
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<String> {
+        [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_match_counts() {
+        assert_eq!(match_counts(&example()).unwrap(), vec![4, 2, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let card = Scratchcard::parse("Card  3:  1 21 53 | 69 82  1").unwrap();
+        assert_eq!(
+            card,
+            Scratchcard {
+                id: 3,
+                winning: vec![1, 21, 53],
+                have: vec![69, 82, 1]
+            }
+        );
+        assert_eq!(card.matches(), 1);
+        assert_eq!(Scratchcard::parse("Card 1: | ").unwrap().matches(), 0);
+        let card = Scratchcard::parse("Card 1: 5 99 128 4000 | 4000 99 6 127").unwrap();
+        assert_eq!(card.matches(), 2);
+        let error = |line: &str| Scratchcard::parse(line).unwrap_err().to_string();
+        assert_eq!(error("Card 1: 1 2 3 4"), "card 1: missing '|'");
+        assert_eq!(
+            error("Card 1: 1 2 1 | 4"),
+            "card 1: winning numbers: duplicate number 1"
+        );
+        assert_eq!(
+            error("Card 1: 1 2 | 4 x"),
+            "card 1: numbers you have: invalid number 'x'"
+        );
+        assert_eq!(error("Card x: 1 | 2"), "invalid card id: 'x'");
+        assert!(Scratchcard::parse("Cards 1: 1 | 2").is_err());
+        assert!(Scratchcard::parse("1 | 2").is_err());
+    }
+
+    #[test]
+    fn test_parse_cards_sequence() {
+        let lines = |lines: &[&str]| lines.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(parse_cards(&example()).unwrap().len(), 6);
+        assert_eq!(
+            parse_cards(&lines(&["", "Card 1: 1 | 1", ""]))
+                .unwrap()
+                .len(),
+            1
+        );
+        let error = parse_cards(&lines(&["Card 1: 1 | 1", "Card 3: 1 | 1"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "line 2: expected card 2, found card 3");
+        let error = parse_cards(&lines(&["Card 1: 1 | 1", "Card 2: 1 | 1", "Card 2: 1 | 1"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "line 3: duplicate card 2");
+        let error = parse_cards(&lines(&["Card 1: 1 | 1", "Card 2: 1 | 1", "Card 1: 1 | 1"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "line 3: duplicate card 1");
+        let error = parse_cards(&lines(&["Card 0: 1 | 1"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "line 1: expected card 1, found card 0");
+        let error = parse_cards(&lines(&["Card 1: 1 | 1", "Card 2: 1 1"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "line 2: card 2: missing '|'");
+    }
+
+    #[test]
+    fn test_count_cards() {
+        assert_eq!(count_cards(&[4, 2, 2, 1, 0, 0]).unwrap(), 30);
+        assert_eq!(count_cards(&[]).unwrap(), 0);
+        assert_eq!(count_cards(&[0, 0, 0]).unwrap(), 3);
+        // wins past the last card are dropped
+        assert_eq!(count_cards(&[5, 1]).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_count_cards_overflow() {
+        // card n has 2^(n-1) copies when every card wins all the rest
+        assert_eq!(count_cards(&[64; 64]).unwrap(), u64::MAX);
+        let error = count_cards(&[79; 80]).unwrap_err().to_string();
+        assert_eq!(error, "card 65: card count overflows u64");
+    }
+
+    #[test]
+    fn test_count_cards_matches_simulation() {
+        let matches = [3, 0, 2, 2, 1, 4, 0, 1, 1, 0];
+        let mut copies = vec![1u64; matches.len()];
+        for n in 0..matches.len() {
+            for next in n + 1..(n + 1 + matches[n]).min(matches.len()) {
+                copies[next] += copies[n];
+            }
+        }
+        assert_eq!(count_cards(&matches).unwrap(), copies.iter().sum::<u64>());
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Error;
+
+// How many points a card is worth for its number of matches. Points are
+// u128 and every step is checked, doubling alone passes u64 at 65 matches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scoring {
+    // 1 point for the first match, doubled for each one after
+    Doubling,
+    // 1 point per match
+    Linear,
+    // the n-th Fibonacci number for n matches: 1, 1, 2, 3, 5, ...
+    Fibonacci,
+    // points for 0, 1, 2, ... matches
+    Table(Vec<u64>),
+}
+
+impl Scoring {
+    // comma separated points for 0, 1, 2, ... matches
+    pub fn parse_table(input: &str) -> Result<Scoring, Error> {
+        let table = input
+            .split(',')
+            .map(|points| {
+                let points = points.trim();
+                points
+                    .parse::<u64>()
+                    .map_err(|_| anyhow::anyhow!("invalid points '{}' in table", points))
+            })
+            .collect::<Result<Vec<u64>, Error>>()?;
+        Ok(Scoring::Table(table))
+    }
+
+    pub fn points(&self, matches: usize) -> Result<u128, Error> {
+        let overflow = || anyhow::anyhow!("{} matches overflow {:?} scoring", matches, self);
+        match self {
+            Scoring::Doubling => match matches {
+                0 => Ok(0),
+                n => u32::try_from(n - 1)
+                    .ok()
+                    .and_then(|shift| 1u128.checked_shl(shift))
+                    .ok_or_else(overflow),
+            },
+            Scoring::Linear => Ok(matches as u128),
+            Scoring::Fibonacci => {
+                if matches == 0 {
+                    return Ok(0);
+                }
+                let (mut a, mut b) = (0u128, 1u128);
+                for _ in 1..matches {
+                    (a, b) = (b, a.checked_add(b).ok_or_else(overflow)?);
+                }
+                Ok(b)
+            }
+            Scoring::Table(table) => table.get(matches).map(|&p| p as u128).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "no points for {} matches in a table of {} entries",
+                    matches,
+                    table.len()
+                )
+            }),
+        }
+    }
+
+    // total over every card, counted from 1 in errors
+    pub fn total(&self, matches: &[usize]) -> Result<u128, Error> {
+        matches
+            .iter()
+            .enumerate()
+            .try_fold(0u128, |total, (n, &count)| {
+                let points = self
+                    .points(count)
+                    .map_err(|e| anyhow::anyhow!("card {}: {}", n + 1, e))?;
+                total
+                    .checked_add(points)
+                    .ok_or_else(|| anyhow::anyhow!("card {}: total points overflow", n + 1))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points() {
+        let points = |scoring: &Scoring| {
+            (0..7)
+                .map(|n| scoring.points(n).unwrap())
+                .collect::<Vec<u128>>()
+        };
+        assert_eq!(points(&Scoring::Doubling), vec![0, 1, 2, 4, 8, 16, 32]);
+        assert_eq!(points(&Scoring::Linear), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(points(&Scoring::Fibonacci), vec![0, 1, 1, 2, 3, 5, 8]);
+        let table = Scoring::parse_table("0, 1, 3, 6, 10, 15, 21").unwrap();
+        assert_eq!(points(&table), vec![0, 1, 3, 6, 10, 15, 21]);
+        assert!(table.points(7).is_err());
+        assert!(Scoring::parse_table("1,x").is_err());
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Scoring::Doubling.points(65).unwrap(), 1 << 64);
+        assert_eq!(Scoring::Doubling.points(128).unwrap(), 1 << 127);
+        assert!(Scoring::Doubling.points(129).is_err());
+        assert!(Scoring::Fibonacci.points(186).is_ok());
+        assert!(Scoring::Fibonacci.points(187).is_err());
+        let error = Scoring::Doubling
+            .total(&[128, 128])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "card 2: total points overflow");
+    }
+
+    #[test]
+    fn test_total() {
+        let matches = [4, 2, 2, 1, 0, 0];
+        assert_eq!(Scoring::Doubling.total(&matches).unwrap(), 13);
+        assert_eq!(Scoring::Linear.total(&matches).unwrap(), 9);
+        assert_eq!(Scoring::Fibonacci.total(&matches).unwrap(), 6);
+        let error = Scoring::Table(vec![0, 1]).total(&matches).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "card 1: no points for 4 matches in a table of 2 entries"
+        );
+    }
+}
@@ -224,7 +224,7 @@ pub fn map_from(map_name: &str) -> &str {
 }
 
 pub fn map_to(map_name: &str) -> &str {
-    map_name.split('-').last().unwrap()
+    map_name.split('-').next_back().unwrap()
 }
 
 #[cfg(test)]
@@ -389,10 +389,10 @@ mod tests {
     #[test]
     fn test_is_in_range() {
         let range_map = RangeMap::new(50, 52, 2);
-        assert_eq!(range_map.is_in_range(49), false);
-        assert_eq!(range_map.is_in_range(50), true);
-        assert_eq!(range_map.is_in_range(51), true);
-        assert_eq!(range_map.is_in_range(52), false);
-        assert_eq!(range_map.is_in_range(53), false);
+        assert!(!range_map.is_in_range(49));
+        assert!(range_map.is_in_range(50));
+        assert!(range_map.is_in_range(51));
+        assert!(!range_map.is_in_range(52));
+        assert!(!range_map.is_in_range(53));
     }
 }
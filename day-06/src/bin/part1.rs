@@ -14,7 +14,7 @@ fn parse_race_table(lines: &[String]) -> Result<Vec<RaceTable>, Error> {
     let mut record_distance = Vec::<usize>::new();
 
     if lines[0].contains("Time") {
-        let time_part = lines[0].split(':').last().unwrap();
+        let time_part = lines[0].split(':').next_back().unwrap();
         let time_token = time_part.split(' ');
         for time_str in time_token {
             let time_str = time_str.trim();
@@ -27,7 +27,7 @@ fn parse_race_table(lines: &[String]) -> Result<Vec<RaceTable>, Error> {
     }
 
     if lines[1].contains("Distance") {
-        let distance_part = lines[1].split(':').last().unwrap();
+        let distance_part = lines[1].split(':').next_back().unwrap();
         let distance_token = distance_part.split(' ');
         for distance_str in distance_token {
             let distance_str = distance_str.trim();
@@ -119,7 +119,7 @@ mod tests {
 
     #[test]
     fn test_parse_table() {
-        let data = vec![
+        let data = [
             "Time:        46     68     98     66\n",
             "Distance:   358   1054   1807   1080",
         ];
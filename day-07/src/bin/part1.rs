@@ -164,11 +164,10 @@ fn is_pair(hand: &str) -> bool {
 fn parse_hand(line: &str) -> Result<Game, Error> {
     let mut parts = line.split_whitespace();
     let hand = parts.next().unwrap().to_string();
-    let bid = parts.next().unwrap().parse::<usize>();
-    if bid.is_err() {
-        return Err(anyhow::anyhow!("Invalid bid {}", bid.unwrap_err()));
-    }
-    let bid = bid.unwrap();
+    let bid = match parts.next().unwrap().parse::<usize>() {
+        Ok(bid) => bid,
+        Err(e) => return Err(anyhow::anyhow!("Invalid bid {}", e)),
+    };
     if !is_hand(&hand) {
         Err(anyhow::anyhow!("Invalid hand: {}", hand))
     } else {
@@ -235,7 +234,7 @@ mod tests {
 
     #[test]
     fn test_parse_game() {
-        let lines = vec!["5432Q 1", "A2345 2"];
+        let lines = ["5432Q 1", "A2345 2"];
         let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
         let game = parse_game(&lines);
         assert_eq!(game.len(), 2);
@@ -247,7 +246,7 @@ mod tests {
 
     #[test]
     fn test_rank_game1() {
-        let lines = vec!["5432Q 1", "A2345 2"];
+        let lines = ["5432Q 1", "A2345 2"];
         let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
         let game = parse_game(&lines);
         let ranked = rank_game(&game);
@@ -260,7 +259,7 @@ mod tests {
 
     #[test]
     fn test_rank_game2() {
-        let lines = vec![
+        let lines = [
             "22222 1", "AAAA3 1", "33322 6", "QQQKT 7", "KKQJT 6", "AKQJT 6",
         ];
         let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
@@ -283,7 +282,7 @@ mod tests {
 
     #[test]
     fn test_rank_game3() {
-        let lines = vec!["22222 1", "AAAA3 2"];
+        let lines = ["22222 1", "AAAA3 2"];
         let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
         let game = parse_game(&lines);
         let ranked = rank_game(&game);
@@ -327,7 +326,7 @@ mod tests {
 
     #[test]
     fn test_rank_cmp_equal() {
-        let lines = vec!["77772 1", "QAAAA 2"];
+        let lines = ["77772 1", "QAAAA 2"];
         let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
         let game = parse_game(&lines);
         let ranked = rank_game(&game);
@@ -448,7 +447,7 @@ mod tests {
 
     #[test]
     fn test_example1() {
-        let lines = vec![
+        let lines = [
             "32T3K 765",
             "T55J5 684",
             "KK677 28",
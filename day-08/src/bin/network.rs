@@ -0,0 +1,161 @@
+use std::io::Read;
+
+use anyhow::Error;
+use clap::{Parser, Subcommand, ValueEnum};
+use day_08::export::{self, Format};
+use day_08::query;
+use day_08::validate::validate;
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = "Advent of Code 2023, Day 8 network tools"
+)]
+struct Args {
+    #[arg(
+        short = 'f',
+        long = "input",
+        help = "Input file to use, stdin if not present",
+        default_value = "-"
+    )]
+    file: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Report every problem found in the network input
+    Validate {
+        #[arg(long, default_value = "AAA")]
+        start: String,
+    },
+    /// Print the network as DOT or Mermaid text
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Dot)]
+        format: ExportFormat,
+        #[arg(long, help = "Overlay the path walked from start to term")]
+        path: bool,
+        #[arg(long, default_value = "AAA")]
+        start: String,
+        #[arg(long, default_value = "ZZZ")]
+        term: String,
+    },
+    /// Print each step of the L/R walk until a node with the given suffix
+    Walk {
+        #[arg(long, default_value = "AAA")]
+        start: String,
+        #[arg(long, default_value = "Z")]
+        until: String,
+        #[arg(long, default_value_t = 100_000)]
+        limit: usize,
+    },
+    /// Shortest path between two nodes choosing L or R freely at each step
+    Shortest {
+        #[arg(long, default_value = "AAA")]
+        from: String,
+        #[arg(long, default_value = "ZZZ")]
+        to: String,
+    },
+    /// Every node reachable from a start node
+    Reachable {
+        #[arg(long, default_value = "AAA")]
+        from: String,
+    },
+    /// Strongly connected components of the network
+    Scc,
+    /// Nodes that can never reach a node ending in Z
+    DeadEnds,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ExportFormat {
+    Dot,
+    Mermaid,
+}
+
+fn read_lines(file: &str) -> Result<Vec<String>, Error> {
+    let mut buf = String::new();
+    if file == "-" {
+        std::io::stdin().read_to_string(&mut buf)?;
+    } else {
+        buf = std::fs::read_to_string(file)?;
+    }
+    Ok(buf.lines().map(|line| line.to_string()).collect())
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let lines = read_lines(&args.file)?;
+    match args.command {
+        Command::Validate { start } => {
+            let diagnostics = validate(&lines, &start);
+            for diagnostic in diagnostics.iter() {
+                println!("{}", diagnostic);
+            }
+            let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+            if errors > 0 {
+                return Err(anyhow::anyhow!("{} error(s) in network input", errors));
+            }
+        }
+        Command::Export {
+            format,
+            path,
+            start,
+            term,
+        } => {
+            let instruction = day_08::parse_lines(lines)?;
+            let path = if path {
+                Some(day_08::trace_path(&instruction, &start, &term)?)
+            } else {
+                None
+            };
+            let format = match format {
+                ExportFormat::Dot => Format::Dot,
+                ExportFormat::Mermaid => Format::Mermaid,
+            };
+            print!("{}", export::export(&instruction, format, path.as_deref()));
+        }
+        Command::Walk {
+            start,
+            until,
+            limit,
+        } => {
+            let instruction = day_08::parse_lines(lines)?;
+            for (step, node, direction) in instruction.walk(&start).take(limit) {
+                println!("{} {} {}", step, direction, node);
+                if node.ends_with(&until) {
+                    break;
+                }
+            }
+        }
+        Command::Shortest { from, to } => {
+            let instruction = day_08::parse_lines(lines)?;
+            match query::shortest_path(&instruction, &from, &to) {
+                Some(path) => println!("{} steps: {}", path.len() - 1, path.join(" -> ")),
+                None => return Err(anyhow::anyhow!("no path from {} to {}", from, to)),
+            }
+        }
+        Command::Reachable { from } => {
+            let instruction = day_08::parse_lines(lines)?;
+            for name in query::reachable(&instruction, &from) {
+                println!("{}", name);
+            }
+        }
+        Command::Scc => {
+            let instruction = day_08::parse_lines(lines)?;
+            for component in query::strongly_connected_components(&instruction) {
+                println!("{}", component.join(" "));
+            }
+        }
+        Command::DeadEnds => {
+            let instruction = day_08::parse_lines(lines)?;
+            for name in query::cannot_reach_terminal(&instruction) {
+                println!("{}", name);
+            }
+        }
+    }
+    Ok(())
+}
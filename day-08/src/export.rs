@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{is_start, is_terminal, Instruction};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+struct Edge<'a> {
+    from: &'a str,
+    to: &'a str,
+    label: &'static str,
+    on_path: bool,
+}
+
+fn sorted_nodes(instruction: &Instruction) -> Vec<&str> {
+    let mut nodes = instruction
+        .route
+        .keys()
+        .map(|name| name.as_str())
+        .collect::<Vec<&str>>();
+    nodes.sort();
+    nodes
+}
+
+fn path_steps(instruction: &Instruction, path: Option<&[String]>) -> HashSet<(String, char)> {
+    let mut steps = HashSet::new();
+    if let Some(path) = path {
+        if !instruction.cycle.is_empty() {
+            for (n, node) in path.iter().take(path.len().saturating_sub(1)).enumerate() {
+                steps.insert((node.clone(), instruction.cycle[n % instruction.cycle.len()]));
+            }
+        }
+    }
+    steps
+}
+
+fn edges<'a>(instruction: &'a Instruction, path: Option<&[String]>) -> Vec<Edge<'a>> {
+    let steps = path_steps(instruction, path);
+    let mut edges = vec![];
+    for name in sorted_nodes(instruction) {
+        let graph = &instruction.route[name];
+        let left_on_path = steps.contains(&(name.to_string(), 'L'));
+        let right_on_path = steps.contains(&(name.to_string(), 'R'));
+        match (graph.left.as_deref(), graph.right.as_deref()) {
+            (Some(left), Some(right)) if left == right => edges.push(Edge {
+                from: name,
+                to: left,
+                label: "L/R",
+                on_path: left_on_path || right_on_path,
+            }),
+            (left, right) => {
+                if let Some(left) = left {
+                    edges.push(Edge {
+                        from: name,
+                        to: left,
+                        label: "L",
+                        on_path: left_on_path,
+                    });
+                }
+                if let Some(right) = right {
+                    edges.push(Edge {
+                        from: name,
+                        to: right,
+                        label: "R",
+                        on_path: right_on_path,
+                    });
+                }
+            }
+        }
+    }
+    edges
+}
+
+//
+// functions
+//
+
+pub fn to_dot(instruction: &Instruction, path: Option<&[String]>) -> String {
+    let mut out = String::from("digraph network {\n");
+    for name in sorted_nodes(instruction) {
+        if is_start(name) {
+            writeln!(out, "    \"{}\" [style=filled, fillcolor=palegreen];", name).unwrap();
+        } else if is_terminal(name) {
+            writeln!(
+                out,
+                "    \"{}\" [style=filled, fillcolor=lightcoral];",
+                name
+            )
+            .unwrap();
+        }
+    }
+    for edge in edges(instruction, path) {
+        if edge.on_path {
+            writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"{}\", color=blue, penwidth=2];",
+                edge.from, edge.to, edge.label
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                edge.from, edge.to, edge.label
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub fn to_mermaid(instruction: &Instruction, path: Option<&[String]>) -> String {
+    let mut out = String::from("flowchart LR\n");
+    let mut on_path = vec![];
+    for (n, edge) in edges(instruction, path).iter().enumerate() {
+        writeln!(out, "    {} -->|{}| {}", edge.from, edge.label, edge.to).unwrap();
+        if edge.on_path {
+            on_path.push(n.to_string());
+        }
+    }
+    let nodes = sorted_nodes(instruction);
+    let starts = nodes
+        .iter()
+        .filter(|name| is_start(name))
+        .copied()
+        .collect::<Vec<&str>>();
+    let terminals = nodes
+        .iter()
+        .filter(|name| !is_start(name) && is_terminal(name))
+        .copied()
+        .collect::<Vec<&str>>();
+    if !starts.is_empty() {
+        out.push_str("    classDef start fill:#98fb98\n");
+        writeln!(out, "    class {} start", starts.join(",")).unwrap();
+    }
+    if !terminals.is_empty() {
+        out.push_str("    classDef terminal fill:#f08080\n");
+        writeln!(out, "    class {} terminal", terminals.join(",")).unwrap();
+    }
+    if !on_path.is_empty() {
+        writeln!(
+            out,
+            "    linkStyle {} stroke:#0000ff,stroke-width:3px",
+            on_path.join(",")
+        )
+        .unwrap();
+    }
+    out
+}
+
+pub fn export(instruction: &Instruction, format: Format, path: Option<&[String]>) -> String {
+    match format {
+        Format::Dot => to_dot(instruction, path),
+        Format::Mermaid => to_mermaid(instruction, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, trace_path};
+
+    fn example() -> Instruction {
+        let lines = [
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        parse_lines(lines).unwrap()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let instruction = example();
+        let expect = [
+            "digraph network {",
+            "    \"AAA\" [style=filled, fillcolor=palegreen];",
+            "    \"ZZZ\" [style=filled, fillcolor=lightcoral];",
+            "    \"AAA\" -> \"BBB\" [label=\"L/R\"];",
+            "    \"BBB\" -> \"AAA\" [label=\"L\"];",
+            "    \"BBB\" -> \"ZZZ\" [label=\"R\"];",
+            "    \"ZZZ\" -> \"ZZZ\" [label=\"L/R\"];",
+            "}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(to_dot(&instruction, None), expect);
+    }
+
+    #[test]
+    fn test_to_dot_path() {
+        let instruction = example();
+        let path = trace_path(&instruction, "AAA", "ZZZ").unwrap();
+        let dot = to_dot(&instruction, Some(&path));
+        assert!(dot.contains("\"AAA\" -> \"BBB\" [label=\"L/R\", color=blue, penwidth=2];"));
+        assert!(dot.contains("\"BBB\" -> \"AAA\" [label=\"L\", color=blue, penwidth=2];"));
+        assert!(dot.contains("\"BBB\" -> \"ZZZ\" [label=\"R\", color=blue, penwidth=2];"));
+        assert!(dot.contains("\"ZZZ\" -> \"ZZZ\" [label=\"L/R\"];"));
+    }
+
+    #[test]
+    fn test_to_dot_quotes_ids() {
+        let lines = [
+            "L",
+            "",
+            "11A = (11B, 11B)",
+            "11B = (11Z, 11Z)",
+            "11Z = (11Z, 11Z)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let dot = to_dot(&parse_lines(lines).unwrap(), None);
+        assert!(dot.contains("    \"11A\" [style=filled, fillcolor=palegreen];"));
+        assert!(dot.contains("    \"11A\" -> \"11B\" [label=\"L/R\"];"));
+        assert!(!dot.contains(" 11A"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let instruction = example();
+        let path = trace_path(&instruction, "AAA", "ZZZ").unwrap();
+        let expect = [
+            "flowchart LR",
+            "    AAA -->|L/R| BBB",
+            "    BBB -->|L| AAA",
+            "    BBB -->|R| ZZZ",
+            "    ZZZ -->|L/R| ZZZ",
+            "    classDef start fill:#98fb98",
+            "    class AAA start",
+            "    classDef terminal fill:#f08080",
+            "    class ZZZ terminal",
+            "    linkStyle 0,1,2 stroke:#0000ff,stroke-width:3px",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(export(&instruction, Format::Mermaid, Some(&path)), expect);
+    }
+}
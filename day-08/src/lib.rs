@@ -0,0 +1,392 @@
+use anyhow::Error;
+use std::collections::{HashMap, HashSet};
+
+pub mod export;
+pub mod query;
+pub mod validate;
+
+pub struct Graph {
+    pub value: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+pub struct Instruction {
+    pub cycle: Vec<char>,
+    pub route: HashMap<String, Graph>,
+}
+
+impl Instruction {
+    pub fn walk<'a>(&'a self, start: &str) -> Walk<'a> {
+        Walk {
+            instruction: self,
+            current: self
+                .route
+                .get_key_value(start)
+                .map(|(name, _)| name.as_str()),
+            step: 0,
+        }
+    }
+}
+
+// Lazily follows the L/R cycle from a start node, yielding the step number,
+// the node arrived at and the direction taken. The walk ends on a node that is
+// not defined or an invalid direction, otherwise it never ends.
+pub struct Walk<'a> {
+    instruction: &'a Instruction,
+    current: Option<&'a str>,
+    step: usize,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (usize, &'a str, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.instruction.route.get(self.current?)?;
+        let direction = *self
+            .instruction
+            .cycle
+            .get(self.step % self.instruction.cycle.len().max(1))?;
+        let next = match direction {
+            'L' => graph.left.as_deref(),
+            'R' => graph.right.as_deref(),
+            _ => None,
+        };
+        self.current = next;
+        self.step += 1;
+        next.map(|node| (self.step, node, direction))
+    }
+}
+
+//
+// functions
+//
+
+// nodes ending in A start a walk and nodes ending in Z end one, as in the puzzle
+pub fn is_start(name: &str) -> bool {
+    name.ends_with('A')
+}
+
+pub fn is_terminal(name: &str) -> bool {
+    name.ends_with('Z')
+}
+
+fn parse_name(name: &str) -> Result<&str, Error> {
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(anyhow::anyhow!("invalid node name: '{}'", name));
+    }
+    Ok(name)
+}
+
+pub fn parse_node(line: &str, graph: &mut Graph) -> Result<(), Error> {
+    let (start, dest) = line
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("expected 'XXX = (AAA, BBB)': {}", line))?;
+    let dest = dest
+        .trim()
+        .strip_prefix('(')
+        .and_then(|dest| dest.strip_suffix(')'))
+        .ok_or_else(|| anyhow::anyhow!("expected '(AAA, BBB)': {}", line))?;
+    let (left, right) = dest
+        .split_once(',')
+        .ok_or_else(|| anyhow::anyhow!("expected 'AAA, BBB': {}", line))?;
+    graph.value = parse_name(start)?.to_string();
+    graph.left = Some(parse_name(left)?.to_string());
+    graph.right = Some(parse_name(right)?.to_string());
+    Ok(())
+}
+
+pub fn parse_lines(lines: Vec<String>) -> Result<Instruction, Error> {
+    let mut instruction = Instruction {
+        cycle: vec![],
+        route: HashMap::new(),
+    };
+    if lines.is_empty() {
+        return Err(anyhow::anyhow!("missing instruction line"));
+    }
+    instruction.cycle = lines[0].trim().to_string().chars().collect();
+    for line in lines[1..].iter() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut graph = Graph {
+            value: String::from(""),
+            left: None,
+            right: None,
+        };
+        if parse_node(line, &mut graph).is_ok() {
+            instruction.route.insert(graph.value.clone(), graph);
+        } else {
+            return Err(anyhow::anyhow!("Failed to parse node:{}", line));
+        }
+    }
+    Ok(instruction)
+}
+
+fn check_cycle(instruction: &Instruction) -> Result<(), Error> {
+    if instruction.cycle.is_empty() {
+        return Err(anyhow::anyhow!("Empty cycle"));
+    }
+    if instruction.cycle.iter().any(|c| *c != 'L' && *c != 'R') {
+        return Err(anyhow::anyhow!("Invalid direction in cycle"));
+    }
+    Ok(())
+}
+
+pub fn trace_path(
+    instruction: &Instruction,
+    start: &str,
+    term: &str,
+) -> Result<Vec<String>, Error> {
+    check_cycle(instruction)?;
+    let mut visited = HashSet::from([(start.to_string(), 0)]);
+    let mut path = vec![start.to_string()];
+    for (step, node, _) in instruction.walk(start) {
+        path.push(node.to_string());
+        if node == term {
+            return Ok(path);
+        }
+        if !visited.insert((node.to_string(), step % instruction.cycle.len())) {
+            return Err(anyhow::anyhow!("cycle detected before reaching {}", term));
+        }
+    }
+    // the walk only ends on a node that is not defined
+    Err(anyhow::anyhow!(
+        "walk stopped at undefined node {} before reaching {}",
+        path[path.len() - 1],
+        term
+    ))
+}
+
+// same walk as `trace_path` without keeping the path
+pub fn count_steps(instruction: &Instruction, start: &str, term: &str) -> Result<usize, Error> {
+    check_cycle(instruction)?;
+    let mut visited = HashSet::from([(start, 0)]);
+    let mut last = start;
+    for (step, node, _) in instruction.walk(start) {
+        if node == term {
+            return Ok(step);
+        }
+        if !visited.insert((node, step % instruction.cycle.len())) {
+            return Err(anyhow::anyhow!("cycle detected before reaching {}", term));
+        }
+        last = node;
+    }
+    Err(anyhow::anyhow!(
+        "walk stopped at undefined node {} before reaching {}",
+        last,
+        term
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node() {
+        let mut graph = Graph {
+            value: String::from(""),
+            left: None,
+            right: None,
+        };
+        let line = "AAA = (BBB, CCC)";
+        assert!(parse_node(line, &mut graph).is_ok());
+        assert_eq!(graph.value, "AAA");
+        assert_eq!(graph.left, Some("BBB".to_string()));
+        assert_eq!(graph.right, Some("CCC".to_string()));
+    }
+
+    #[test]
+    fn test_parse_node_malformed() {
+        let mut graph = Graph {
+            value: String::from(""),
+            left: None,
+            right: None,
+        };
+        assert!(parse_node("AAA", &mut graph).is_err());
+        assert!(parse_node("AAA = BBB, CCC", &mut graph).is_err());
+        assert!(parse_node("AAA = (BBB CCC)", &mut graph).is_err());
+        assert!(parse_node("AAA = (, CCC)", &mut graph).is_err());
+        assert!(parse_node(" = (BBB, CCC)", &mut graph).is_err());
+        assert!(parse_node("AAA = ()", &mut graph).is_err());
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = [
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "BBB = (DDD, EEE)",
+            "CCC = (ZZZ, GGG)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        assert_eq!(instruction.cycle, vec!['R', 'L']);
+        assert_eq!(instruction.route.len(), 3);
+        assert_eq!(instruction.route["AAA"].value, "AAA");
+        assert_eq!(instruction.route["AAA"].left, Some("BBB".to_string()));
+        assert_eq!(instruction.route["AAA"].right, Some("CCC".to_string()));
+        assert_eq!(instruction.route["BBB"].value, "BBB");
+        assert_eq!(instruction.route["BBB"].left, Some("DDD".to_string()));
+        assert_eq!(instruction.route["BBB"].right, Some("EEE".to_string()));
+        assert_eq!(instruction.route["CCC"].value, "CCC");
+        assert_eq!(instruction.route["CCC"].left, Some("ZZZ".to_string()));
+        assert_eq!(instruction.route["CCC"].right, Some("GGG".to_string()));
+    }
+
+    #[test]
+    fn test_parse_lines_empty() {
+        assert!(parse_lines(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_count_steps_example1() {
+        let lines = [
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "BBB = (DDD, EEE)",
+            "CCC = (ZZZ, GGG)",
+            "DDD = (DDD, DDD)",
+            "EEE = (EEE, EEE)",
+            "GGG = (GGG, GGG)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        assert_eq!(count_steps(&instruction, "AAA", "ZZZ").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_count_steps_example2() {
+        let lines = [
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        assert_eq!(count_steps(&instruction, "AAA", "ZZZ").unwrap(), 6);
+    }
+
+    #[test]
+    fn test_trace_path() {
+        let lines = [
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        let path = trace_path(&instruction, "AAA", "ZZZ").unwrap();
+        assert_eq!(path, vec!["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]);
+    }
+
+    #[test]
+    fn test_walk() {
+        let lines = [
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        let steps = instruction.walk("AAA").take(4).collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            vec![
+                (1, "BBB", 'L'),
+                (2, "AAA", 'L'),
+                (3, "BBB", 'R'),
+                (4, "AAA", 'L')
+            ]
+        );
+        let found = instruction
+            .walk("AAA")
+            .find(|(_, node, _)| node.ends_with('Z'));
+        assert_eq!(found, Some((6, "ZZZ", 'R')));
+        let stops = HashSet::from(["ZZZ"]);
+        let found = instruction
+            .walk("BBB")
+            .find(|(_, node, _)| stops.contains(node));
+        assert_eq!(found, Some((3, "ZZZ", 'R')));
+        let sampled = instruction
+            .walk("AAA")
+            .step_by(3)
+            .take(3)
+            .map(|(step, _, _)| step)
+            .collect::<Vec<usize>>();
+        assert_eq!(sampled, vec![1, 4, 7]);
+        assert_eq!(instruction.walk("QQQ").next(), None);
+    }
+
+    #[test]
+    fn test_walk_dangling() {
+        let lines = ["L", "", "AAA = (BBB, BBB)"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        assert_eq!(
+            instruction.walk("AAA").collect::<Vec<_>>(),
+            vec![(1, "BBB", 'L')]
+        );
+    }
+
+    #[test]
+    fn test_trace_path_dangling() {
+        let lines = [
+            "LL",
+            "",
+            "AAA = (BBB, CCC)",
+            "BBB = (DDD, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        let error = trace_path(&instruction, "AAA", "ZZZ").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "walk stopped at undefined node DDD before reaching ZZZ"
+        );
+        let error = count_steps(&instruction, "AAA", "ZZZ").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "walk stopped at undefined node DDD before reaching ZZZ"
+        );
+        assert!(count_steps(&instruction, "XXX", "ZZZ").is_err());
+    }
+
+    #[test]
+    fn test_trace_path_cycle() {
+        let lines = ["L", "", "AAA = (BBB, ZZZ)", "BBB = (AAA, ZZZ)"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let instruction = parse_lines(lines).unwrap();
+        assert!(trace_path(&instruction, "AAA", "ZZZ").is_err());
+        let error = count_steps(&instruction, "AAA", "ZZZ").unwrap_err();
+        assert_eq!(error.to_string(), "cycle detected before reaching ZZZ");
+    }
+}
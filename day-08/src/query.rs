@@ -0,0 +1,246 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use crate::{is_terminal, Instruction};
+
+// successors that are defined in the network, ignoring the L/R cycle
+fn neighbours<'a>(instruction: &'a Instruction, name: &str) -> Vec<&'a str> {
+    match instruction.route.get(name) {
+        Some(graph) => [graph.left.as_deref(), graph.right.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|target| instruction.route.contains_key(*target))
+            .collect(),
+        None => vec![],
+    }
+}
+
+//
+// functions
+//
+
+pub fn shortest_path(instruction: &Instruction, from: &str, to: &str) -> Option<Vec<String>> {
+    let (from, _) = instruction.route.get_key_value(from)?;
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([from.as_str()]);
+    previous.insert(from, from);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![current.to_string()];
+            let mut node = current;
+            while node != from {
+                node = previous[node];
+                path.push(node.to_string());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbours(instruction, current) {
+            if !previous.contains_key(next) {
+                previous.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+pub fn reachable(instruction: &Instruction, from: &str) -> BTreeSet<String> {
+    let mut visited = BTreeSet::new();
+    if !instruction.route.contains_key(from) {
+        return visited;
+    }
+    let mut queue = VecDeque::from([from]);
+    visited.insert(from.to_string());
+    while let Some(current) = queue.pop_front() {
+        for next in neighbours(instruction, current) {
+            if visited.insert(next.to_string()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    visited
+}
+
+// Tarjan's algorithm with an explicit stack so long chains do not overflow
+pub fn strongly_connected_components(instruction: &Instruction) -> Vec<Vec<String>> {
+    let mut nodes = instruction
+        .route
+        .keys()
+        .map(|name| name.as_str())
+        .collect::<Vec<&str>>();
+    nodes.sort();
+    let position = nodes
+        .iter()
+        .enumerate()
+        .map(|(n, name)| (*name, n))
+        .collect::<HashMap<&str, usize>>();
+    let successors = nodes
+        .iter()
+        .map(|name| {
+            neighbours(instruction, name)
+                .iter()
+                .map(|next| position[next])
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
+
+    let mut index: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut lowlink = vec![0; nodes.len()];
+    let mut on_stack = vec![false; nodes.len()];
+    let mut stack = vec![];
+    let mut next_index = 0;
+    let mut components = vec![];
+
+    for root in 0..nodes.len() {
+        if index[root].is_some() {
+            continue;
+        }
+        let mut call_stack = vec![(root, 0)];
+        index[root] = Some(next_index);
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((node, child)) = call_stack.pop() {
+            if child < successors[node].len() {
+                call_stack.push((node, child + 1));
+                let next = successors[node][child];
+                match index[next] {
+                    None => {
+                        index[next] = Some(next_index);
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        call_stack.push((next, 0));
+                    }
+                    Some(found) if on_stack[next] => {
+                        lowlink[node] = lowlink[node].min(found);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            if Some(lowlink[node]) == index[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(nodes[member].to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+            if let Some((parent, _)) = call_stack.last() {
+                lowlink[*parent] = lowlink[*parent].min(lowlink[node]);
+            }
+        }
+    }
+    components.sort();
+    components
+}
+
+pub fn cannot_reach_terminal(instruction: &Instruction) -> BTreeSet<String> {
+    let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+    for name in instruction.route.keys() {
+        for next in neighbours(instruction, name) {
+            predecessors.entry(next).or_default().push(name);
+        }
+    }
+    let mut can_reach = instruction
+        .route
+        .keys()
+        .map(|name| name.as_str())
+        .filter(|name| is_terminal(name))
+        .collect::<BTreeSet<&str>>();
+    let mut queue = can_reach.iter().copied().collect::<VecDeque<&str>>();
+    while let Some(current) = queue.pop_front() {
+        for previous in predecessors.get(current).into_iter().flatten() {
+            if can_reach.insert(previous) {
+                queue.push_back(previous);
+            }
+        }
+    }
+    instruction
+        .route
+        .keys()
+        .filter(|name| !can_reach.contains(name.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_lines;
+
+    fn example() -> Instruction {
+        let lines = [
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "BBB = (DDD, EEE)",
+            "CCC = (ZZZ, GGG)",
+            "DDD = (DDD, DDD)",
+            "EEE = (EEE, BBB)",
+            "GGG = (GGG, GGG)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+        parse_lines(lines).unwrap()
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let instruction = example();
+        assert_eq!(
+            shortest_path(&instruction, "AAA", "ZZZ"),
+            Some(vec![
+                "AAA".to_string(),
+                "CCC".to_string(),
+                "ZZZ".to_string()
+            ])
+        );
+        assert_eq!(
+            shortest_path(&instruction, "AAA", "AAA"),
+            Some(vec!["AAA".to_string()])
+        );
+        assert_eq!(shortest_path(&instruction, "BBB", "ZZZ"), None);
+        assert_eq!(shortest_path(&instruction, "QQQ", "ZZZ"), None);
+    }
+
+    #[test]
+    fn test_reachable() {
+        let instruction = example();
+        let expect = ["BBB", "DDD", "EEE"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<BTreeSet<String>>();
+        assert_eq!(reachable(&instruction, "BBB"), expect);
+        assert_eq!(reachable(&instruction, "AAA").len(), 7);
+        assert!(reachable(&instruction, "QQQ").is_empty());
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let instruction = example();
+        let components = strongly_connected_components(&instruction);
+        assert_eq!(components.len(), 6);
+        assert!(components.contains(&vec!["BBB".to_string(), "EEE".to_string()]));
+        assert!(components.contains(&vec!["AAA".to_string()]));
+    }
+
+    #[test]
+    fn test_cannot_reach_terminal() {
+        let instruction = example();
+        let expect = ["BBB", "DDD", "EEE", "GGG"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<BTreeSet<String>>();
+        assert_eq!(cannot_reach_terminal(&instruction), expect);
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::{parse_node, Graph};
+
+#[derive(Debug, PartialEq)]
+pub enum Issue {
+    MissingInstruction,
+    InvalidInstruction { column: usize, found: char },
+    MalformedNode { reason: String },
+    DuplicateNode { name: String, first_line: usize },
+    DanglingReference { name: String, target: String },
+    UndefinedStart { name: String },
+    Unreachable { name: String, start: String },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::MissingInstruction => write!(f, "missing L/R instruction line"),
+            Issue::InvalidInstruction { column, found } => {
+                write!(f, "invalid instruction '{}' at column {}", found, column)
+            }
+            Issue::MalformedNode { reason } => write!(f, "malformed node: {}", reason),
+            Issue::DuplicateNode { name, first_line } => {
+                write!(f, "node {} already defined on line {}", name, first_line)
+            }
+            Issue::DanglingReference { name, target } => {
+                write!(f, "node {} refers to undefined node {}", name, target)
+            }
+            Issue::UndefinedStart { name } => write!(f, "start node {} is not defined", name),
+            Issue::Unreachable { name, start } => {
+                write!(f, "node {} is unreachable from {}", name, start)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub issue: Issue,
+}
+
+impl Diagnostic {
+    // unreachable nodes are legal input, anything else prevents a walk
+    pub fn is_error(&self) -> bool {
+        !matches!(self.issue, Issue::Unreachable { .. })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.issue)
+    }
+}
+
+//
+// functions
+//
+
+pub fn validate(lines: &[String], start: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let cycle = lines.first().map(|line| line.trim()).unwrap_or("");
+    if cycle.is_empty() {
+        diagnostics.push(Diagnostic {
+            line: 1,
+            issue: Issue::MissingInstruction,
+        });
+    }
+    for (column, found) in cycle.chars().enumerate() {
+        if found != 'L' && found != 'R' {
+            diagnostics.push(Diagnostic {
+                line: 1,
+                issue: Issue::InvalidInstruction {
+                    column: column + 1,
+                    found,
+                },
+            });
+        }
+    }
+
+    let mut defined: HashMap<String, usize> = HashMap::new();
+    let mut nodes: Vec<(usize, Graph)> = vec![];
+    for (n, line) in lines.iter().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut graph = Graph {
+            value: String::from(""),
+            left: None,
+            right: None,
+        };
+        if let Err(e) = parse_node(line, &mut graph) {
+            diagnostics.push(Diagnostic {
+                line: n + 1,
+                issue: Issue::MalformedNode {
+                    reason: e.to_string(),
+                },
+            });
+            continue;
+        }
+        if let Some(first_line) = defined.get(&graph.value) {
+            diagnostics.push(Diagnostic {
+                line: n + 1,
+                issue: Issue::DuplicateNode {
+                    name: graph.value.clone(),
+                    first_line: *first_line,
+                },
+            });
+            continue;
+        }
+        defined.insert(graph.value.clone(), n + 1);
+        nodes.push((n + 1, graph));
+    }
+
+    for (line, graph) in nodes.iter() {
+        for target in [&graph.left, &graph.right].into_iter().flatten() {
+            if !defined.contains_key(target) {
+                diagnostics.push(Diagnostic {
+                    line: *line,
+                    issue: Issue::DanglingReference {
+                        name: graph.value.clone(),
+                        target: target.clone(),
+                    },
+                });
+            }
+        }
+    }
+
+    if !defined.contains_key(start) {
+        diagnostics.push(Diagnostic {
+            line: 1,
+            issue: Issue::UndefinedStart {
+                name: start.to_string(),
+            },
+        });
+    } else {
+        let route = nodes
+            .iter()
+            .map(|(_, graph)| (graph.value.as_str(), graph))
+            .collect::<HashMap<&str, &Graph>>();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let graph = route[current];
+            for target in [&graph.left, &graph.right].into_iter().flatten() {
+                if route.contains_key(target.as_str()) && visited.insert(target.as_str()) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        for (line, graph) in nodes.iter() {
+            if !visited.contains(graph.value.as_str()) {
+                diagnostics.push(Diagnostic {
+                    line: *line,
+                    issue: Issue::Unreachable {
+                        name: graph.value.clone(),
+                        start: start.to_string(),
+                    },
+                });
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_example() {
+        let lines = to_lines(&[
+            "LLR",
+            "",
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]);
+        assert!(validate(&lines, "AAA").is_empty());
+    }
+
+    #[test]
+    fn test_validate_missing_instruction() {
+        let diagnostics = validate(&[], "AAA");
+        assert_eq!(diagnostics[0].issue, Issue::MissingInstruction);
+        assert!(diagnostics.iter().all(|d| d.is_error()));
+    }
+
+    #[test]
+    fn test_validate_invalid_instruction() {
+        let lines = to_lines(&["LXR", "", "AAA = (AAA, AAA)"]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                line: 1,
+                issue: Issue::InvalidInstruction {
+                    column: 2,
+                    found: 'X'
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_malformed_node() {
+        let lines = to_lines(&["LR", "", "AAA = (AAA, AAA)", "BBB = CCC"]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 4);
+        assert!(matches!(diagnostics[0].issue, Issue::MalformedNode { .. }));
+    }
+
+    #[test]
+    fn test_validate_duplicate_node() {
+        let lines = to_lines(&["LR", "", "AAA = (AAA, AAA)", "AAA = (AAA, AAA)"]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                line: 4,
+                issue: Issue::DuplicateNode {
+                    name: "AAA".to_string(),
+                    first_line: 3
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_dangling_reference() {
+        let lines = to_lines(&["LR", "", "AAA = (BBB, ZZZ)", "ZZZ = (ZZZ, ZZZ)"]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                line: 3,
+                issue: Issue::DanglingReference {
+                    name: "AAA".to_string(),
+                    target: "BBB".to_string()
+                },
+            }]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "line 3: node AAA refers to undefined node BBB"
+        );
+    }
+
+    #[test]
+    fn test_validate_undefined_start() {
+        let lines = to_lines(&["LR", "", "BBB = (BBB, BBB)"]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(
+            diagnostics[0].issue,
+            Issue::UndefinedStart {
+                name: "AAA".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_unreachable() {
+        let lines = to_lines(&[
+            "LR",
+            "",
+            "AAA = (ZZZ, ZZZ)",
+            "BBB = (CCC, ZZZ)",
+            "CCC = (BBB, BBB)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]);
+        let diagnostics = validate(&lines, "AAA");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line, 4);
+        assert_eq!(diagnostics[1].line, 5);
+        assert!(diagnostics.iter().all(|d| !d.is_error()));
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::Error;
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// A rectangular grid stored row by row. Positions are (row, column) from the
+// top left, and every lookup is bounds checked so nothing wraps to the
+// opposite edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, Error> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        if width * height != cells.len() {
+            return Err(anyhow::anyhow!(
+                "{} cells do not fill rows of width {}",
+                cells.len(),
+                width
+            ));
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        if self.contains(row, column) {
+            self.cells.get(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        if self.contains(row, column) {
+            self.cells.get_mut(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    // the position `delta` away, if it is on the grid
+    pub fn offset(
+        &self,
+        row: usize,
+        column: usize,
+        delta: (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(delta.0)?;
+        let column = column.checked_add_signed(delta.1)?;
+        if self.contains(row, column) {
+            Some((row, column))
+        } else {
+            None
+        }
+    }
+
+    // up, left, right and down neighbours that are on the grid
+    pub fn neighbours4(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |delta| self.offset(row, column, *delta))
+    }
+
+    // orthogonal and diagonal neighbours that are on the grid
+    pub fn neighbours8(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        SURROUNDING
+            .iter()
+            .filter_map(move |delta| self.offset(row, column, *delta))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn column(&self, column: usize) -> Option<impl Iterator<Item = &T>> {
+        if column < self.width {
+            Some(self.cells.iter().skip(column).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |column| self.cells.iter().skip(column).step_by(self.width))
+    }
+
+    // every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(n, cell)| ((n / self.width, n % self.width), cell))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    // one row per line, every line must have the same number of characters
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, Error> {
+        let width = lines
+            .first()
+            .map_or(0, |line| line.as_ref().chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (n, line) in lines.iter().enumerate() {
+            let before = cells.len();
+            cells.extend(line.as_ref().chars());
+            if cells.len() - before != width {
+                return Err(anyhow::anyhow!(
+                    "line {}: expected {} characters, found {}",
+                    n + 1,
+                    width,
+                    cells.len() - before
+                ));
+            }
+        }
+        Ok(Grid {
+            width,
+            height: if width == 0 { 0 } else { lines.len() },
+            cells,
+        })
+    }
+
+    // one row per line, lines shorter than the longest are filled with `fill`
+    pub fn from_lines_padded<S: AsRef<str>>(lines: &[S], fill: char) -> Self {
+        let width = lines
+            .iter()
+            .map(|line| line.as_ref().chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            let before = cells.len();
+            cells.extend(line.as_ref().chars());
+            cells.resize(before + width, fill);
+        }
+        Grid {
+            width,
+            height: if width == 0 { 0 } else { lines.len() },
+            cells,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::from_lines(&["abc", "def"]).unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let grid = example();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert!(Grid::from_lines(&["abc", "de"]).is_err());
+        assert!(Grid::from_lines(&["abc", "defg"]).is_err());
+        assert_eq!(Grid::from_lines(&["é1"]).unwrap().width(), 2);
+        let empty = Grid::from_lines::<&str>(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.height(), 0);
+    }
+
+    #[test]
+    fn test_from_lines_padded() {
+        let grid = Grid::from_lines_padded(&["ab", "", "abcd"], '.');
+        assert_eq!(grid, Grid::from_lines(&["ab..", "....", "abcd"]).unwrap());
+        assert!(Grid::from_lines_padded::<&str>(&[], '.').is_empty());
+        assert!(Grid::from_lines_padded(&["", ""], '.').is_empty());
+    }
+
+    #[test]
+    fn test_get_bounds() {
+        let mut grid = example();
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.offset(0, 0, (-1, 0)), None);
+        assert_eq!(grid.offset(0, 2, (1, -1)), Some((1, 1)));
+        *grid.get_mut(0, 0).unwrap() = 'z';
+        assert_eq!(grid.get(0, 0), Some(&'z'));
+        assert!(grid.get_mut(0, 9).is_none());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8(0, 1).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+        assert_eq!(grid.neighbours4(1, 1).count(), 3);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(
+            grid.column(1).unwrap().collect::<String>(),
+            "be".to_string()
+        );
+        assert!(grid.column(3).is_none());
+        let columns = grid
+            .columns()
+            .map(|column| column.collect::<String>())
+            .collect::<Vec<String>>();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = example().transpose();
+        assert_eq!(grid, Grid::from_lines(&["ad", "be", "cf"]).unwrap());
+        assert_eq!(grid.transpose(), example());
+    }
+
+    #[test]
+    fn test_new_and_map() {
+        let grid = Grid::new(2, vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.map(|n| n * 10).get(1, 0), Some(&30));
+        assert!(Grid::new(3, vec![1, 2, 3, 4]).is_err());
+        assert!(Grid::new(0, vec![1]).is_err());
+        assert_eq!(Grid::filled(2, 3, '.').iter().count(), 6);
+        let positions = example()
+            .iter()
+            .filter(|(_, c)| **c == 'e')
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![(1, 1)]);
+    }
+}